@@ -1,5 +1,8 @@
 use clap::{crate_version, Arg, ArgGroup, Command};
-use sablier_thread_program::state::{SerializableInstruction, Trigger};
+use sablier_cron::Dialect;
+use sablier_thread_program::state::{
+    CronExclusions, CronOptions, SerializableInstruction, ThreadBudget, ThreadLifetime, Trigger,
+};
 use solana_sdk::{pubkey::Pubkey, signature::Keypair};
 
 use crate::parser::ProgramInfo;
@@ -17,6 +20,8 @@ pub enum CliCommand {
     // Crontab
     Crontab {
         schedule: String,
+        dialect: Option<Dialect>,
+        thread: Option<Pubkey>,
     },
 
     // Delegation
@@ -78,10 +83,18 @@ pub enum CliCommand {
         domain: String,
         kickoff_instruction: SerializableInstruction,
         trigger: Trigger,
+        address_lookup_tables: Option<Vec<Pubkey>>,
     },
     ThreadDelete {
         id: String,
     },
+    ThreadDeposit {
+        id: String,
+        amount: u64,
+    },
+    ThreadMigrate {
+        id: String,
+    },
     ThreadGet {
         id: Option<String>,
         address: Option<Pubkey>,
@@ -99,6 +112,13 @@ pub enum CliCommand {
         id: String,
         rate_limit: Option<u64>,
         schedule: Option<String>,
+        lifetime: Option<ThreadLifetime>,
+        cron: Option<CronOptions>,
+        cron_exclusions: Option<CronExclusions>,
+        compute_unit_price: Option<u64>,
+        address_lookup_tables: Option<Vec<Pubkey>>,
+        budget: Option<ThreadBudget>,
+        reserve: Option<u64>,
     },
 
     // Registry
@@ -163,6 +183,20 @@ pub fn app() -> Command {
                         .index(1)
                         .required(true)
                         .help("The schedule to generate a cron table for"),
+                )
+                .arg(
+                    Arg::new("dialect")
+                        .long("dialect")
+                        .short('d')
+                        .value_name("DIALECT")
+                        .help("Require the schedule to be written in this dialect (unix, seconds or years)"),
+                )
+                .arg(
+                    Arg::new("thread")
+                        .long("thread")
+                        .short('t')
+                        .value_name("ADDRESS")
+                        .help("Hash the 'H' tokens of the schedule with the address of this thread"),
                 ),
         )
         .subcommand(
@@ -512,6 +546,24 @@ pub fn app() -> Command {
                             ArgGroup::new("trigger")
                                 .args(["account", "cron", "immediate"])
                                 .required(true),
+                        )
+                        .arg(
+                            Arg::new("not_before")
+                                .long("not_before")
+                                .value_name("UNIX_TIMESTAMP")
+                                .help("Only allow the trigger to fire at or after this timestamp"),
+                        )
+                        .arg(
+                            Arg::new("not_after")
+                                .long("not_after")
+                                .value_name("UNIX_TIMESTAMP")
+                                .help("Only allow the trigger to fire at or before this timestamp"),
+                        )
+                        .arg(
+                            Arg::new("address_lookup_tables")
+                                .long("address_lookup_tables")
+                                .value_name("ADDRESSES")
+                                .help("Comma-separated address lookup tables to compile the thread's transactions with"),
                         ),
                 )
                 .subcommand(
@@ -525,6 +577,25 @@ pub fn app() -> Command {
                             .help("The id of the thread to delete"),
                     ),
                 )
+                .subcommand(
+                    Command::new("deposit")
+                        .about("Deposit lamports into a thread")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("id")
+                                .index(1)
+                                .required(false)
+                                .help("The id of the thread to deposit into"),
+                        )
+                        .arg(
+                            Arg::new("amount")
+                                .long("amount")
+                                .short('a')
+                                .required(true)
+                                .value_name("LAMPORTS")
+                                .help("The number of lamports to deposit"),
+                        ),
+                )
                 .subcommand(
                     Command::new("get")
                         .about("Lookup a thread")
@@ -543,6 +614,17 @@ pub fn app() -> Command {
                                 .help("The address of the thread to lookup"),
                         )
                 )
+                .subcommand(
+                    Command::new("migrate")
+                        .about("Migrate a thread to the current account layout")
+                        .arg_required_else_help(true)
+                        .arg(
+                            Arg::new("id")
+                                .index(1)
+                                .required(false)
+                                .help("The id of the thread to migrate"),
+                        ),
+                )
                 .subcommand(
                     Command::new("pause")
                         .about("Pause a thread")
@@ -595,6 +677,82 @@ pub fn app() -> Command {
                                 .short('s')
                                 .required(false)
                                 .help("The cron schedule of the thread"),
+                        )
+                        .arg(
+                            Arg::new("not_before")
+                                .long("not_before")
+                                .value_name("UNIX_TIMESTAMP")
+                                .help("Don't kick off the thread before this timestamp"),
+                        )
+                        .arg(
+                            Arg::new("not_after")
+                                .long("not_after")
+                                .value_name("UNIX_TIMESTAMP")
+                                .help("Close the thread to its authority after this timestamp"),
+                        )
+                        .arg(
+                            Arg::new("max_executions")
+                                .long("max_executions")
+                                .help("Close the thread to its authority after this number of executions"),
+                        )
+                        .arg(
+                            Arg::new("max_catch_up")
+                                .long("max_catch_up")
+                                .help("The maximum number of missed cron moments to replay, coalescing the rest into a single run"),
+                        )
+                        .arg(
+                            Arg::new("dialect")
+                                .long("dialect")
+                                .value_name("DIALECT")
+                                .help("Require the cron schedule to be written in this dialect (unix, seconds or years)"),
+                        )
+                        .arg(
+                            Arg::new("exclude_dates")
+                                .long("exclude_dates")
+                                .value_name("DATES")
+                                .help("Comma-separated dates (YYYY-MM-DD) on which the cron schedule doesn't fire, replacing the excluded dates and schedule"),
+                        )
+                        .arg(
+                            Arg::new("exclude_schedule")
+                                .long("exclude_schedule")
+                                .value_name("SCHEDULE")
+                                .help("A cron schedule whose moments are excluded, replacing the excluded dates and schedule"),
+                        )
+                        .arg(
+                            Arg::new("compute_unit_price")
+                                .long("compute_unit_price")
+                                .value_name("MICRO_LAMPORTS")
+                                .help("The maximum compute unit price of the priority fees reimbursed to workers, 0 for none"),
+                        )
+                        .arg(
+                            Arg::new("address_lookup_tables")
+                                .long("address_lookup_tables")
+                                .value_name("ADDRESSES")
+                                .help("Comma-separated address lookup tables to compile the thread's transactions with, replacing the current ones"),
+                        )
+                        .arg(
+                            Arg::new("max_reimbursement")
+                                .long("max_reimbursement")
+                                .value_name("LAMPORTS")
+                                .help("The maximum number of lamports an instruction may spend from the worker, reimbursed by the thread"),
+                        )
+                        .arg(
+                            Arg::new("max_spending")
+                                .long("max_spending")
+                                .value_name("LAMPORTS")
+                                .help("Pause the thread once it spends this number of lamports in a budget window"),
+                        )
+                        .arg(
+                            Arg::new("budget_window")
+                                .long("budget_window")
+                                .value_name("SECONDS")
+                                .help("The length of the budget window, e.g. 86400 for a daily budget"),
+                        )
+                        .arg(
+                            Arg::new("reserve")
+                                .long("reserve")
+                                .value_name("LAMPORTS")
+                                .help("Pause the thread when its balance would fall below this number of lamports above rent exemption"),
                         ),
                 ),
         )
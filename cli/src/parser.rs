@@ -1,7 +1,12 @@
 use std::{convert::TryFrom, fs, path::PathBuf, str::FromStr};
 
+use chrono::{DateTime, NaiveDate};
 use clap::ArgMatches;
-use sablier_thread_program::state::{SerializableAccount, SerializableInstruction, Trigger};
+use sablier_cron::{Dialect, Schedule};
+use sablier_thread_program::state::{
+    CronDialect, CronExclusions, CronOptions, SerializableAccount, SerializableInstruction,
+    ThreadBudget, ThreadLifetime, Trigger, TriggerCondition,
+};
 use serde::{Deserialize as JsonDeserialize, Serialize as JsonSerialize};
 use solana_sdk::{
     pubkey::Pubkey,
@@ -99,8 +104,11 @@ fn parse_config_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
 }
 
 fn parse_crontab_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
+    let dialect = parse_dialect(matches)?;
     Ok(CliCommand::Crontab {
-        schedule: parse_string("schedule", matches)?,
+        schedule: parse_schedule("schedule", dialect, matches)?,
+        dialect,
+        thread: parse_pubkey("thread", matches).ok(),
     })
 }
 
@@ -171,14 +179,22 @@ fn parse_thread_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
             domain: parse_string("domain", matches)?,
             kickoff_instruction: parse_instruction_file("kickoff_instruction", matches)?,
             trigger: parse_trigger(matches)?,
+            address_lookup_tables: parse_pubkeys("address_lookup_tables", matches)?,
         }),
         Some(("delete", matches)) => Ok(CliCommand::ThreadDelete {
             id: parse_string("id", matches)?,
         }),
+        Some(("deposit", matches)) => Ok(CliCommand::ThreadDeposit {
+            id: parse_string("id", matches)?,
+            amount: parse_u64("amount", matches)?,
+        }),
         Some(("get", matches)) => Ok(CliCommand::ThreadGet {
             id: parse_string("id", matches).ok(),
             address: parse_pubkey("address", matches).ok(),
         }),
+        Some(("migrate", matches)) => Ok(CliCommand::ThreadMigrate {
+            id: parse_string("id", matches)?,
+        }),
         Some(("pause", matches)) => Ok(CliCommand::ThreadPause {
             id: parse_string("id", matches)?,
         }),
@@ -188,11 +204,24 @@ fn parse_thread_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
         Some(("reset", matches)) => Ok(CliCommand::ThreadReset {
             id: parse_string("id", matches)?,
         }),
-        Some(("update", matches)) => Ok(CliCommand::ThreadUpdate {
-            id: parse_string("id", matches)?,
-            rate_limit: parse_u64("rate_limit", matches).ok(),
-            schedule: parse_string("schedule", matches).ok(),
-        }),
+        Some(("update", matches)) => {
+            let dialect = parse_dialect(matches)?;
+            Ok(CliCommand::ThreadUpdate {
+                id: parse_string("id", matches)?,
+                rate_limit: parse_u64("rate_limit", matches).ok(),
+                schedule: matches
+                    .contains_id("schedule")
+                    .then(|| parse_schedule("schedule", dialect, matches))
+                    .transpose()?,
+                lifetime: parse_lifetime(matches)?,
+                cron: parse_cron_options(dialect, matches)?,
+                cron_exclusions: parse_cron_exclusions(dialect, matches)?,
+                compute_unit_price: parse_u64("compute_unit_price", matches).ok(),
+                address_lookup_tables: parse_pubkeys("address_lookup_tables", matches)?,
+                budget: parse_budget(matches)?,
+                reserve: parse_u64("reserve", matches).ok(),
+            })
+        }
         _ => Err(CliError::CommandNotRecognized(
             matches.subcommand().unwrap().0.into(),
         )),
@@ -230,6 +259,31 @@ fn parse_worker_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
 // Arg parsers
 
 fn parse_trigger(matches: &ArgMatches) -> Result<Trigger, CliError> {
+    let trigger = parse_primary_trigger(matches)?;
+
+    // If any conditions are given, gate the trigger on them.
+    let mut conditions = vec![];
+    if matches.contains_id("not_before") {
+        conditions.push(TriggerCondition::NotBefore {
+            unix_ts: _parse_i64("not_before", matches)?,
+        });
+    }
+    if matches.contains_id("not_after") {
+        conditions.push(TriggerCondition::NotAfter {
+            unix_ts: _parse_i64("not_after", matches)?,
+        });
+    }
+    if conditions.is_empty() {
+        return Ok(trigger);
+    }
+
+    Ok(Trigger::Compound {
+        trigger: trigger.into(),
+        conditions,
+    })
+}
+
+fn parse_primary_trigger(matches: &ArgMatches) -> Result<Trigger, CliError> {
     if matches.contains_id("account") {
         return Ok(Trigger::Account {
             address: parse_pubkey("address", matches)?,
@@ -238,7 +292,7 @@ fn parse_trigger(matches: &ArgMatches) -> Result<Trigger, CliError> {
         });
     } else if matches.contains_id("cron") {
         return Ok(Trigger::Cron {
-            schedule: parse_string("cron", matches)?,
+            schedule: parse_schedule("cron", None, matches)?,
             skippable: true,
         });
     } else if matches.contains_id("now") {
@@ -248,6 +302,150 @@ fn parse_trigger(matches: &ArgMatches) -> Result<Trigger, CliError> {
     Err(CliError::BadParameter("trigger".into()))
 }
 
+fn parse_cron_options(
+    dialect: Option<Dialect>,
+    matches: &ArgMatches,
+) -> Result<Option<CronOptions>, CliError> {
+    if !matches.contains_id("max_catch_up") && dialect.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(CronOptions {
+        max_catch_up: matches
+            .contains_id("max_catch_up")
+            .then(|| parse_u64("max_catch_up", matches))
+            .transpose()?,
+        dialect: dialect.map(CronDialect::from),
+    }))
+}
+
+fn parse_cron_exclusions(
+    dialect: Option<Dialect>,
+    matches: &ArgMatches,
+) -> Result<Option<CronExclusions>, CliError> {
+    if !matches.contains_id("exclude_dates") && !matches.contains_id("exclude_schedule") {
+        return Ok(None);
+    }
+
+    let mut dates = match matches.contains_id("exclude_dates") {
+        true => parse_string("exclude_dates", matches)?
+            .split(',')
+            .map(|date| parse_excluded_date(date.trim()))
+            .collect::<Result<Vec<u16>, CliError>>()?,
+        false => vec![],
+    };
+    dates.sort_unstable();
+    dates.dedup();
+
+    Ok(Some(CronExclusions {
+        dates,
+        schedule: matches
+            .contains_id("exclude_schedule")
+            .then(|| parse_schedule("exclude_schedule", dialect, matches))
+            .transpose()?,
+    }))
+}
+
+/// Parses a date into the number of days since 1970-01-01, as the thread program stores excluded dates.
+fn parse_excluded_date(date: &str) -> Result<u16, CliError> {
+    NaiveDate::from_str(date)
+        .ok()
+        .and_then(|date| {
+            let days = date.signed_duration_since(DateTime::UNIX_EPOCH.date_naive());
+            u16::try_from(days.num_days()).ok()
+        })
+        .ok_or_else(|| CliError::BadParameter(format!("exclude_dates: {}", date)))
+}
+
+fn parse_dialect(matches: &ArgMatches) -> Result<Option<Dialect>, CliError> {
+    matches
+        .contains_id("dialect")
+        .then(|| {
+            Dialect::from_str(&parse_string("dialect", matches)?)
+                .map_err(|err| CliError::BadParameter(format!("dialect: {}", err)))
+        })
+        .transpose()
+}
+
+/// Parses a cron schedule, failing early on schedules the thread program would reject.
+fn parse_schedule(
+    arg: &str,
+    dialect: Option<Dialect>,
+    matches: &ArgMatches,
+) -> Result<String, CliError> {
+    let schedule = parse_string(arg, matches)?;
+    match dialect {
+        Some(dialect) => Schedule::parse_as(&schedule, dialect).map(|_| ()),
+        None => Schedule::from_str(&schedule).map(|_| ()),
+    }
+    .map_err(|err| schedule_error(arg, &schedule, err))?;
+    Ok(schedule)
+}
+
+/// Reports an invalid schedule, underlining the invalid token if the error points at one.
+pub fn schedule_error(arg: &str, schedule: &str, err: sablier_cron::error::Error) -> CliError {
+    match err.token() {
+        Some(token) => CliError::BadParameter(format!(
+            "{}: {}\n\n    {}\n    {}{}",
+            arg,
+            err,
+            schedule,
+            " ".repeat(schedule[..token.offset].chars().count()),
+            "^".repeat(token.token.chars().count().max(1))
+        )),
+        None => CliError::BadParameter(format!("{}: {}", arg, err)),
+    }
+}
+
+fn parse_lifetime(matches: &ArgMatches) -> Result<Option<ThreadLifetime>, CliError> {
+    if !["not_before", "not_after", "max_executions"]
+        .iter()
+        .any(|arg| matches.contains_id(arg))
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(ThreadLifetime {
+        not_before: matches
+            .contains_id("not_before")
+            .then(|| _parse_i64("not_before", matches))
+            .transpose()?,
+        not_after: matches
+            .contains_id("not_after")
+            .then(|| _parse_i64("not_after", matches))
+            .transpose()?,
+        max_executions: matches
+            .contains_id("max_executions")
+            .then(|| parse_u64("max_executions", matches))
+            .transpose()?,
+    }))
+}
+
+fn parse_budget(matches: &ArgMatches) -> Result<Option<ThreadBudget>, CliError> {
+    if !["max_reimbursement", "max_spending", "budget_window"]
+        .iter()
+        .any(|arg| matches.contains_id(arg))
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(ThreadBudget {
+        max_reimbursement: matches
+            .contains_id("max_reimbursement")
+            .then(|| parse_u64("max_reimbursement", matches))
+            .transpose()?,
+        max_spending: matches
+            .contains_id("max_spending")
+            .then(|| parse_u64("max_spending", matches))
+            .transpose()?,
+        window: matches
+            .contains_id("budget_window")
+            .then(|| _parse_i64("budget_window", matches))
+            .transpose()?
+            .unwrap_or_default(),
+    }))
+}
+
 fn parse_instruction_file(
     arg: &str,
     matches: &ArgMatches,
@@ -269,6 +467,20 @@ fn parse_pubkey(arg: &str, matches: &ArgMatches) -> Result<Pubkey, CliError> {
         .map_err(|_err| CliError::BadParameter(arg.into()))
 }
 
+/// Parses a comma-separated list of pubkeys, possibly empty.
+fn parse_pubkeys(arg: &str, matches: &ArgMatches) -> Result<Option<Vec<Pubkey>>, CliError> {
+    if !matches.contains_id(arg) {
+        return Ok(None);
+    }
+    parse_string(arg, matches)?
+        .split(',')
+        .map(str::trim)
+        .filter(|pubkey| !pubkey.is_empty())
+        .map(|pubkey| Pubkey::from_str(pubkey).map_err(|_err| CliError::BadParameter(arg.into())))
+        .collect::<Result<Vec<Pubkey>, CliError>>()
+        .map(Some)
+}
+
 fn parse_string(arg: &str, matches: &ArgMatches) -> Result<String, CliError> {
     matches
         .get_one::<String>(arg)
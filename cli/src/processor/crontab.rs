@@ -1,16 +1,35 @@
 use chrono::DateTime;
-use sablier_cron::Schedule;
-use std::str::FromStr;
+use sablier_cron::{Dialect, Schedule};
+use solana_sdk::pubkey::Pubkey;
 
-use crate::{client::Client, errors::CliError};
+use crate::{client::Client, errors::CliError, parser::schedule_error};
 
-pub fn get(client: &Client, schedule: String) -> Result<(), CliError> {
+pub fn get(
+    client: &Client,
+    schedule: String,
+    dialect: Option<Dialect>,
+    thread: Option<Pubkey>,
+) -> Result<(), CliError> {
     let clock = client.get_clock().unwrap();
-    let schedule = Schedule::from_str(schedule.as_str()).unwrap();
+    // 'H' tokens hash the address of the thread, as they do on-chain.
+    let seed = thread.as_ref().map_or(&[][..], |thread| thread.as_ref());
+    let schedule = match dialect {
+        Some(dialect) => Schedule::parse_as_seeded(schedule.as_str(), dialect, seed),
+        None => Schedule::parse_seeded(schedule.as_str(), seed),
+    }
+    .map_err(|err| schedule_error("schedule", &schedule, err))?;
+    println!("Schedule: {} ({:#})", schedule, schedule);
+    match schedule.dialect() {
+        Some(dialect) => println!("Dialect: {} ({} fields)", dialect, dialect.fields()),
+        None => println!("Dialect: shorthand"),
+    }
 
     let mut i = 0;
     for t in schedule.after(&DateTime::from_timestamp(clock.unix_timestamp, 0).unwrap()) {
-        println!("{:#?}", t);
+        match schedule.timezone() {
+            Some(timezone) => println!("{:#?} ({})", t, t.with_timezone(&timezone)),
+            None => println!("{:#?}", t),
+        }
         i += 1;
         if i > 8 {
             break;
@@ -46,7 +46,11 @@ pub fn process(matches: &ArgMatches) -> Result<(), CliError> {
             epoch_thread,
             hasher_thread,
         } => config::set(&client, admin, epoch_thread, hasher_thread),
-        CliCommand::Crontab { schedule } => crontab::get(&client, schedule),
+        CliCommand::Crontab {
+            schedule,
+            dialect,
+            thread,
+        } => crontab::get(&client, schedule, dialect, thread),
         CliCommand::DelegationCreate { worker_id } => delegation::create(&client, worker_id),
         CliCommand::DelegationDeposit {
             amount,
@@ -95,8 +99,18 @@ pub fn process(matches: &ArgMatches) -> Result<(), CliError> {
             domain,
             kickoff_instruction,
             trigger,
-        } => thread::create(&client, id, domain, vec![kickoff_instruction], trigger),
+            address_lookup_tables,
+        } => thread::create(
+            &client,
+            id,
+            domain,
+            vec![kickoff_instruction],
+            trigger,
+            address_lookup_tables,
+        ),
         CliCommand::ThreadDelete { id } => thread::delete(&client, id),
+        CliCommand::ThreadDeposit { id, amount } => thread::deposit(&client, id, amount),
+        CliCommand::ThreadMigrate { id } => thread::migrate(&client, id),
         CliCommand::ThreadPause { id } => thread::pause(&client, id),
         CliCommand::ThreadResume { id } => thread::resume(&client, id),
         CliCommand::ThreadReset { id } => thread::reset(&client, id),
@@ -108,7 +122,26 @@ pub fn process(matches: &ArgMatches) -> Result<(), CliError> {
             id,
             rate_limit,
             schedule,
-        } => thread::update(&client, id, rate_limit, schedule),
+            lifetime,
+            cron,
+            cron_exclusions,
+            compute_unit_price,
+            address_lookup_tables,
+            budget,
+            reserve,
+        } => thread::update(
+            &client,
+            id,
+            rate_limit,
+            schedule,
+            lifetime,
+            cron,
+            cron_exclusions,
+            compute_unit_price,
+            address_lookup_tables,
+            budget,
+            reserve,
+        ),
         CliCommand::RegistryGet => registry::get(&client),
         CliCommand::RegistryUnlock => registry::unlock(&client),
         CliCommand::WorkerCreate { signatory } => worker::create(&client, signatory, false),
@@ -2,8 +2,10 @@ use anchor_lang::{
     solana_program::{instruction::Instruction, system_program},
     AccountDeserialize, InstructionData, ToAccountMetas,
 };
+use sablier_cron::Schedule;
 use sablier_thread_program::state::{
-    SerializableInstruction, Thread, ThreadSettings, Trigger, VersionedThread,
+    CronExclusions, CronOptions, SerializableInstruction, Thread, ThreadBudget, ThreadLifetime,
+    ThreadSettings, Trigger, VersionedThread,
 };
 use sablier_utils::CrateInfo;
 use solana_sdk::pubkey::Pubkey;
@@ -30,6 +32,7 @@ pub fn create(
     domain: String,
     instructions: Vec<SerializableInstruction>,
     trigger: Trigger,
+    address_lookup_tables: Option<Vec<Pubkey>>,
 ) -> Result<(), CliError> {
     let thread_pubkey = Thread::pubkey(
         client.payer_pubkey(),
@@ -54,7 +57,27 @@ pub fn create(
         }
         .data(),
     };
-    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    let mut ixs = vec![ix];
+
+    // Attach the address lookup tables to the thread in the same transaction.
+    if address_lookup_tables.is_some() {
+        let settings = ThreadSettings {
+            fee: None,
+            instructions: None,
+            name: None,
+            rate_limit: None,
+            trigger: None,
+            lifetime: None,
+            cron: None,
+            cron_exclusions: None,
+            compute_unit_price: None,
+            address_lookup_tables,
+            budget: None,
+            reserve: None,
+        };
+        ixs.push(update_instruction(client, thread_pubkey, settings));
+    }
+    client.send_and_confirm(&ixs, &[client.payer()]).unwrap();
     get(client, thread_pubkey)?;
     Ok(())
 }
@@ -75,10 +98,61 @@ pub fn delete(client: &Client, id: String) -> Result<(), CliError> {
     Ok(())
 }
 
+pub fn deposit(client: &Client, id: String, amount: u64) -> Result<(), CliError> {
+    let thread_pubkey = Thread::pubkey(client.payer_pubkey(), id.into_bytes(), None);
+    let ix = Instruction {
+        program_id: sablier_thread_program::ID,
+        accounts: sablier_thread_program::accounts::ThreadDeposit {
+            payer: client.payer_pubkey(),
+            system_program: system_program::ID,
+            thread: thread_pubkey,
+        }
+        .to_account_metas(Some(false)),
+        data: sablier_thread_program::instruction::ThreadDeposit { amount }.data(),
+    };
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, thread_pubkey)?;
+    Ok(())
+}
+
 pub fn get(client: &Client, address: Pubkey) -> Result<(), CliError> {
     let data = client.get_account_data(&address).unwrap();
     let thread = VersionedThread::try_deserialize(&mut data.as_slice()).unwrap();
     println!("Address: {}\n{:#?}", address, thread);
+    if let Trigger::Cron { schedule, .. } = thread.trigger().primary() {
+        let parsed = match thread.extension().cron.dialect {
+            Some(dialect) => Schedule::parse_as_seeded(schedule, dialect.into(), address.as_ref()),
+            None => Schedule::parse_seeded(schedule, address.as_ref()),
+        };
+        if let Ok(parsed) = parsed {
+            println!("Schedule: {} ({:#})", schedule, parsed);
+        }
+        let exclusions = thread.extension().cron_exclusions;
+        let dates: Vec<String> = exclusions.dates().map(|date| date.to_string()).collect();
+        if !dates.is_empty() {
+            println!("Excluded dates: {}", dates.join(", "));
+        }
+        if let Some(schedule) = exclusions.schedule {
+            println!("Excluded schedule: {}", schedule);
+        }
+    }
+    Ok(())
+}
+
+pub fn migrate(client: &Client, id: String) -> Result<(), CliError> {
+    let thread_pubkey = Thread::pubkey(client.payer_pubkey(), id.into_bytes(), None);
+    let ix = Instruction {
+        program_id: sablier_thread_program::ID,
+        accounts: sablier_thread_program::accounts::ThreadMigrate {
+            payer: client.payer_pubkey(),
+            system_program: system_program::ID,
+            thread: thread_pubkey,
+        }
+        .to_account_metas(Some(false)),
+        data: sablier_thread_program::instruction::ThreadMigrate {}.data(),
+    };
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, thread_pubkey)?;
     Ok(())
 }
 
@@ -130,11 +204,19 @@ pub fn reset(client: &Client, id: String) -> Result<(), CliError> {
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn update(
     client: &Client,
     id: String,
     rate_limit: Option<u64>,
     schedule: Option<String>,
+    lifetime: Option<ThreadLifetime>,
+    cron: Option<CronOptions>,
+    cron_exclusions: Option<CronExclusions>,
+    compute_unit_price: Option<u64>,
+    address_lookup_tables: Option<Vec<Pubkey>>,
+    budget: Option<ThreadBudget>,
+    reserve: Option<u64>,
 ) -> Result<(), CliError> {
     let thread_pubkey = Thread::pubkey(client.payer_pubkey(), id.into_bytes(), None);
     let trigger = schedule.map(|schedule| Trigger::Cron {
@@ -147,8 +229,26 @@ pub fn update(
         name: None,
         rate_limit,
         trigger,
+        lifetime,
+        cron,
+        cron_exclusions,
+        compute_unit_price,
+        address_lookup_tables,
+        budget,
+        reserve,
     };
-    let ix = Instruction {
+    let ix = update_instruction(client, thread_pubkey, settings);
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    get(client, thread_pubkey)?;
+    Ok(())
+}
+
+fn update_instruction(
+    client: &Client,
+    thread_pubkey: Pubkey,
+    settings: ThreadSettings,
+) -> Instruction {
+    Instruction {
         program_id: sablier_thread_program::ID,
         accounts: sablier_thread_program::accounts::ThreadUpdate {
             payer: client.payer_pubkey(),
@@ -158,10 +258,7 @@ pub fn update(
         }
         .to_account_metas(Some(false)),
         data: sablier_thread_program::instruction::ThreadUpdate { settings }.data(),
-    };
-    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
-    get(client, thread_pubkey)?;
-    Ok(())
+    }
 }
 
 pub fn parse_pubkey_from_id_or_address(
@@ -0,0 +1,58 @@
+//! Compares finding the next moment of schedules from their expression, as kickoffs used to, and from their
+//! compiled form. Run with `cargo bench -p sablier-cron`.
+//!
+//! Timings are measured on the host. On-chain compute units are logged at kickoff by the thread program built with
+//! its `compute-units` feature.
+
+use chrono::{TimeZone, Utc};
+use sablier_cron::{CompiledSchedule, Schedule};
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 2_000;
+
+const EXPRESSIONS: [&str; 6] = [
+    "0 */5 * * * *",
+    "0 30 9 * * Mon-Fri",
+    "0 0 0 1 1 * 2030",
+    "0 0 12 LW * *",
+    "0 0 8 * * FRI#3",
+    "CRON_TZ=America/New_York 0 30 9 * * Mon-Fri",
+];
+
+fn time(f: impl Fn()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let after = Utc.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap();
+    println!(
+        "{:<46} {:>12} {:>12} {:>12}",
+        "expression", "parse+eval", "decode+eval", "eval"
+    );
+    for expression in EXPRESSIONS {
+        let bytes = Schedule::from_str(expression).unwrap().compile().to_bytes();
+        let compiled = CompiledSchedule::from_bytes(&bytes).unwrap();
+
+        let parsed = time(|| {
+            let schedule = Schedule::from_str(black_box(expression)).unwrap();
+            black_box(schedule.next_after(&after));
+        });
+        let decoded = time(|| {
+            let schedule = CompiledSchedule::from_bytes(black_box(&bytes)).unwrap();
+            black_box(schedule.next_after(&after));
+        });
+        let evaluated = time(|| {
+            black_box(black_box(&compiled).next_after(&after));
+        });
+        println!(
+            "{:<46} {:>12?} {:>12?} {:>12?}",
+            expression, parsed, decoded, evaluated
+        );
+    }
+}
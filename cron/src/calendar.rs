@@ -0,0 +1,297 @@
+use chrono::offset::LocalResult;
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Timelike, Utc};
+use std::collections::BTreeSet;
+use std::ops::{BitAnd, BitOr, Sub};
+
+use crate::compiled::CompiledSchedule;
+use crate::schedule::{resolve_local, Schedule};
+use crate::timezone::Tz;
+
+/// A set of moments composed of schedules and dates, e.g. "every weekday at 14:00 except exchange holidays".
+///
+/// Calendars are built from schedules, compiled schedules and dates, and combined with `union` (`|`),
+/// `intersection` (`&`) and `except` (`-`):
+///
+/// ```
+/// use chrono::{NaiveDate, TimeZone, Utc};
+/// use sablier_cron::{Calendar, Dates, Schedule};
+/// use std::str::FromStr;
+///
+/// let weekdays = Schedule::from_str("0 0 14 * * Mon-Fri").unwrap();
+/// let holidays = Dates::new([NaiveDate::from_ymd_opt(2025, 12, 25).unwrap()]);
+/// let calendar = Calendar::from(weekdays) - holidays;
+/// let next = calendar.next_after(&Utc.with_ymd_and_hms(2025, 12, 24, 15, 0, 0).unwrap());
+/// assert_eq!(next, Some(Utc.with_ymd_and_hms(2025, 12, 26, 14, 0, 0).unwrap()));
+/// ```
+#[derive(Clone, Debug)]
+pub enum Calendar {
+    /// The moments of a schedule.
+    Schedule(Schedule),
+    /// The moments of a compiled schedule.
+    Compiled(CompiledSchedule),
+    /// Every second of a set of dates.
+    Dates(Dates),
+    /// The moments of any of the calendars.
+    Union(Vec<Calendar>),
+    /// The moments of all the calendars.
+    Intersection(Vec<Calendar>),
+    /// The moments of the first calendar which are not moments of the second one.
+    Difference(Box<Calendar>, Box<Calendar>),
+}
+
+/// A set of dates, each of them including every second of the day in its timezone.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Dates {
+    dates: BTreeSet<NaiveDate>,
+    timezone: Option<Tz>,
+}
+
+impl Dates {
+    /// Creates a set of dates, in UTC unless a timezone is set with `in_timezone`.
+    pub fn new(dates: impl IntoIterator<Item = NaiveDate>) -> Dates {
+        Dates {
+            dates: dates.into_iter().collect(),
+            timezone: None,
+        }
+    }
+
+    /// Sets the timezone the dates start and end in, e.g. the timezone of the schedule they are excluded from.
+    pub fn in_timezone(self, timezone: Option<Tz>) -> Dates {
+        Dates { timezone, ..self }
+    }
+
+    /// Returns the timezone the dates start and end in, if it's not UTC.
+    pub fn timezone(&self) -> Option<Tz> {
+        self.timezone
+    }
+
+    /// Provides an iterator over the dates, in chronological order.
+    pub fn iter(&self) -> impl Iterator<Item = &NaiveDate> {
+        self.dates.iter()
+    }
+
+    fn includes(&self, moment: &DateTime<Utc>) -> bool {
+        self.dates.contains(&self.date_of(moment))
+    }
+
+    fn next_after(&self, after: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let next = after.with_nanosecond(0)? + Duration::seconds(1);
+        let date = self.date_of(&next);
+        if self.dates.contains(&date) {
+            return Some(next);
+        }
+        let date = self.dates.range(date..).next()?;
+        Some(self.start_of(date))
+    }
+
+    /// Returns the last second of the run of consecutive dates including `moment`.
+    fn included_until(&self, moment: &DateTime<Utc>) -> DateTime<Utc> {
+        let mut date = self.date_of(moment);
+        while let Some(next) = date.succ_opt().filter(|next| self.dates.contains(next)) {
+            date = next;
+        }
+        match date.succ_opt() {
+            Some(next) => self.start_of(&next) - Duration::seconds(1),
+            None => *moment,
+        }
+    }
+
+    fn date_of(&self, moment: &DateTime<Utc>) -> NaiveDate {
+        match self.timezone {
+            Some(timezone) => moment.with_timezone(&timezone).date_naive(),
+            None => moment.date_naive(),
+        }
+    }
+
+    /// Returns the first moment of a date.
+    fn start_of(&self, date: &NaiveDate) -> DateTime<Utc> {
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+        match self.timezone {
+            Some(timezone) => match resolve_local(&timezone, &midnight) {
+                LocalResult::Single(start) | LocalResult::Ambiguous(start, _) => {
+                    start.with_timezone(&Utc)
+                }
+                LocalResult::None => unreachable!(),
+            },
+            None => midnight.and_utc(),
+        }
+    }
+}
+
+impl Calendar {
+    /// Returns the calendar of the moments of either calendar.
+    pub fn union(self, other: impl Into<Calendar>) -> Calendar {
+        match self {
+            Calendar::Union(mut calendars) => {
+                calendars.push(other.into());
+                Calendar::Union(calendars)
+            }
+            calendar => Calendar::Union(vec![calendar, other.into()]),
+        }
+    }
+
+    /// Returns the calendar of the moments of both calendars.
+    pub fn intersection(self, other: impl Into<Calendar>) -> Calendar {
+        match self {
+            Calendar::Intersection(mut calendars) => {
+                calendars.push(other.into());
+                Calendar::Intersection(calendars)
+            }
+            calendar => Calendar::Intersection(vec![calendar, other.into()]),
+        }
+    }
+
+    /// Returns the calendar of the moments of this calendar which are not moments of `excluded`.
+    pub fn except(self, excluded: impl Into<Calendar>) -> Calendar {
+        Calendar::Difference(Box::new(self), Box::new(excluded.into()))
+    }
+
+    /// Returns the first moment of the calendar after `after`.
+    pub fn next_after<Z>(&self, after: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        self.next_after_utc(&after.with_timezone(&Utc))
+            .map(|moment| moment.with_timezone(&after.timezone()))
+    }
+
+    /// Provides an iterator over the moments of the calendar after `after`.
+    pub fn after<'a, Z>(&'a self, after: &DateTime<Z>) -> impl Iterator<Item = DateTime<Z>> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        std::iter::successors(self.next_after(after), move |moment| {
+            self.next_after(moment)
+        })
+    }
+
+    /// Returns true if `date_time` is a moment of the calendar, ignoring its fractions of a second.
+    pub fn includes<Z>(&self, date_time: DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        match date_time.with_timezone(&Utc).with_nanosecond(0) {
+            Some(moment) => self.includes_utc(&moment),
+            None => false,
+        }
+    }
+
+    fn next_after_utc(&self, after: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Calendar::Schedule(schedule) => schedule.next_after(after),
+            Calendar::Compiled(schedule) => schedule.next_after(after),
+            Calendar::Dates(dates) => dates.next_after(after),
+            Calendar::Union(calendars) => calendars
+                .iter()
+                .filter_map(|calendar| calendar.next_after_utc(after))
+                .min(),
+            Calendar::Intersection(calendars) => {
+                // Leapfrog to the latest next moment of the calendars until they all agree on it.
+                let mut after = *after;
+                loop {
+                    let moments = calendars
+                        .iter()
+                        .map(|calendar| calendar.next_after_utc(&after))
+                        .collect::<Option<Vec<_>>>()?;
+                    let latest = *moments.iter().max()?;
+                    if moments.iter().all(|moment| *moment == latest) {
+                        return Some(latest);
+                    }
+                    after = latest - Duration::seconds(1);
+                }
+            }
+            Calendar::Difference(calendar, excluded) => {
+                let mut after = *after;
+                loop {
+                    let moment = calendar.next_after_utc(&after)?;
+                    if !excluded.includes_utc(&moment) {
+                        return Some(moment);
+                    }
+                    // Skip the excluded moments following this one at once, e.g. the rest of an excluded date.
+                    after = excluded.included_until(&moment);
+                }
+            }
+        }
+    }
+
+    fn includes_utc(&self, moment: &DateTime<Utc>) -> bool {
+        match self {
+            // The moments of schedules are those they find, including shifted ones around daylight saving time.
+            Calendar::Schedule(_) | Calendar::Compiled(_) => {
+                self.next_after_utc(&(*moment - Duration::seconds(1))) == Some(*moment)
+            }
+            Calendar::Dates(dates) => dates.includes(moment),
+            Calendar::Union(calendars) => calendars
+                .iter()
+                .any(|calendar| calendar.includes_utc(moment)),
+            Calendar::Intersection(calendars) => calendars
+                .iter()
+                .all(|calendar| calendar.includes_utc(moment)),
+            Calendar::Difference(calendar, excluded) => {
+                calendar.includes_utc(moment) && !excluded.includes_utc(moment)
+            }
+        }
+    }
+
+    /// Returns a moment from which the calendar includes every second up to `moment`, included.
+    /// `moment` must be a moment of the calendar.
+    fn included_until(&self, moment: &DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Calendar::Dates(dates) => dates.included_until(moment),
+            Calendar::Union(calendars) => calendars
+                .iter()
+                .filter(|calendar| calendar.includes_utc(moment))
+                .map(|calendar| calendar.included_until(moment))
+                .max()
+                .unwrap_or(*moment),
+            Calendar::Intersection(calendars) => calendars
+                .iter()
+                .map(|calendar| calendar.included_until(moment))
+                .min()
+                .unwrap_or(*moment),
+            _ => *moment,
+        }
+    }
+}
+
+impl From<Schedule> for Calendar {
+    fn from(schedule: Schedule) -> Calendar {
+        Calendar::Schedule(schedule)
+    }
+}
+
+impl From<CompiledSchedule> for Calendar {
+    fn from(schedule: CompiledSchedule) -> Calendar {
+        Calendar::Compiled(schedule)
+    }
+}
+
+impl From<Dates> for Calendar {
+    fn from(dates: Dates) -> Calendar {
+        Calendar::Dates(dates)
+    }
+}
+
+impl<T: Into<Calendar>> BitOr<T> for Calendar {
+    type Output = Calendar;
+
+    fn bitor(self, other: T) -> Calendar {
+        self.union(other)
+    }
+}
+
+impl<T: Into<Calendar>> BitAnd<T> for Calendar {
+    type Output = Calendar;
+
+    fn bitand(self, other: T) -> Calendar {
+        self.intersection(other)
+    }
+}
+
+impl<T: Into<Calendar>> Sub<T> for Calendar {
+    type Output = Calendar;
+
+    fn sub(self, excluded: T) -> Calendar {
+        self.except(excluded)
+    }
+}
@@ -0,0 +1,410 @@
+use chrono::offset::LocalResult;
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, TimeZone, Timelike};
+
+use crate::error::{Error, ErrorKind};
+use crate::ordinal::{Ordinal, OrdinalSet};
+use crate::schedule::{days_in_month, local_datetime, resolve_local, Schedule};
+use crate::time_unit::*;
+use crate::timezone::{Tz, TZ_LEN};
+
+/// The version of the binary form of compiled schedules, written in its first byte.
+const VERSION: u8 = 1;
+
+const DAYS_UNION: u8 = 1;
+const LAST_WEEKDAY: u8 = 1 << 1;
+const TIMEZONE: u8 = 1 << 2;
+
+/// The number of years a schedule may span, from 1970 to 2100.
+const YEARS: u32 = 131;
+
+/// A schedule compiled into bitsets, evaluated without parsing the expression nor allocating.
+///
+/// Compiled schedules have a fixed-size binary form of `CompiledSchedule::LEN` bytes, so they can be stored once the
+/// expression has been validated and evaluated cheaply later on, e.g. on-chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompiledSchedule {
+    /// Bit `n` is set if second `n` is included.
+    seconds: u64,
+    /// Bit `n` is set if minute `n` is included.
+    minutes: u64,
+    /// Bit `n` is set if hour `n` is included.
+    hours: u32,
+    /// Bit `n` is set if day of month `n` is included.
+    days_of_month: u32,
+    /// Bit `n` is set if month `n` is included.
+    months: u16,
+    /// Bit `n` is set if day of week `n` is included, from 1 (Sunday) to 7 (Saturday).
+    days_of_week: u8,
+    /// Bit `n` is set if year `1970 + n` is included.
+    years: [u64; 3],
+    /// True if days match either field rather than both.
+    days_union: bool,
+    /// Bit `n` is set if the `n`th day before the last day of the month is included ('L-n').
+    last_days: u32,
+    /// True if the last weekday of the month is included ('LW').
+    last_weekday: bool,
+    /// Bit `n` is set if the weekday nearest to day `n` is included ('nW').
+    nearest_weekdays: u32,
+    /// Bit `n` is set if the last day of week `n` of the month is included ('nL').
+    last_of_week: u8,
+    /// Bit `n` of item `k` is set if the `k + 1`th day of week `n` of the month is included ('n#k').
+    nth_of_week: [u8; 5],
+    timezone: Option<Tz>,
+}
+
+impl Schedule {
+    /// Compiles the schedule into bitsets, see [CompiledSchedule](struct.CompiledSchedule.html).
+    pub fn compile(&self) -> CompiledSchedule {
+        let fields = &self.fields;
+        let mut years = [0; 3];
+        for year in fields.years.ordinals() {
+            let index = year - Years::inclusive_min();
+            years[index as usize / 64] |= 1 << (index % 64);
+        }
+        let mut compiled = CompiledSchedule {
+            seconds: bits(&fields.seconds.ordinals()),
+            minutes: bits(&fields.minutes.ordinals()),
+            hours: bits(&fields.hours.ordinals()) as u32,
+            days_of_month: bits(&fields.days_of_month.ordinals()) as u32,
+            months: bits(&fields.months.ordinals()) as u16,
+            days_of_week: bits(&fields.days_of_week.ordinals()) as u8,
+            years,
+            days_union: fields.days_union,
+            last_days: 0,
+            last_weekday: false,
+            nearest_weekdays: 0,
+            last_of_week: 0,
+            nth_of_week: [0; 5],
+            timezone: self.timezone,
+        };
+        let day_rules = fields
+            .days_of_month
+            .day_rules()
+            .iter()
+            .chain(fields.days_of_week.day_rules());
+        for day_rule in day_rules {
+            match *day_rule {
+                DayRule::Last(offset) => compiled.last_days |= 1 << offset,
+                DayRule::LastWeekday => compiled.last_weekday = true,
+                DayRule::NearestWeekday(day) => compiled.nearest_weekdays |= 1 << day,
+                DayRule::LastOfWeek(day) => compiled.last_of_week |= 1 << day,
+                DayRule::NthOfWeek(day, nth) => compiled.nth_of_week[nth as usize - 1] |= 1 << day,
+            }
+        }
+        compiled
+    }
+}
+
+impl CompiledSchedule {
+    /// The length of the binary form of compiled schedules.
+    pub const LEN: usize = 59 + TZ_LEN;
+
+    /// Encodes the schedule into its binary form.
+    pub fn to_bytes(&self) -> [u8; CompiledSchedule::LEN] {
+        let mut flags = 0;
+        if self.days_union {
+            flags |= DAYS_UNION;
+        }
+        if self.last_weekday {
+            flags |= LAST_WEEKDAY;
+        }
+        if self.timezone.is_some() {
+            flags |= TIMEZONE;
+        }
+
+        let mut writer = Writer::default();
+        writer.put(VERSION.into(), 1);
+        writer.put(flags.into(), 1);
+        writer.put(self.seconds, 8);
+        writer.put(self.minutes, 8);
+        writer.put(self.hours.into(), 3);
+        writer.put(self.days_of_month.into(), 4);
+        writer.put(self.months.into(), 2);
+        writer.put(self.days_of_week.into(), 1);
+        writer.put(self.years[0], 8);
+        writer.put(self.years[1], 8);
+        writer.put(self.years[2], 1);
+        writer.put(self.last_days.into(), 4);
+        writer.put(self.nearest_weekdays.into(), 4);
+        writer.put(self.last_of_week.into(), 1);
+        for days_of_week in self.nth_of_week {
+            writer.put(days_of_week.into(), 1);
+        }
+        if let Some(timezone) = self.timezone {
+            writer.bytes[writer.offset..].copy_from_slice(&timezone.to_bytes());
+        }
+        writer.bytes
+    }
+
+    /// Decodes a schedule from its binary form, rejecting values no expression compiles to.
+    pub fn from_bytes(bytes: &[u8]) -> Result<CompiledSchedule, Error> {
+        let invalid = |reason: &str| Error::from(ErrorKind::Compiled(reason.to_owned()));
+        let bytes: &[u8; CompiledSchedule::LEN] = bytes.try_into().map_err(|_| {
+            invalid(&format!(
+                "expected {} bytes, found {}",
+                CompiledSchedule::LEN,
+                bytes.len()
+            ))
+        })?;
+
+        let mut reader = Reader { bytes, offset: 0 };
+        if reader.take(1) != VERSION.into() {
+            return Err(invalid("unsupported version"));
+        }
+        let flags = reader.take(1) as u8;
+        let compiled = CompiledSchedule {
+            seconds: reader.take(8),
+            minutes: reader.take(8),
+            hours: reader.take(3) as u32,
+            days_of_month: reader.take(4) as u32,
+            months: reader.take(2) as u16,
+            days_of_week: reader.take(1) as u8,
+            years: [reader.take(8), reader.take(8), reader.take(1)],
+            days_union: flags & DAYS_UNION != 0,
+            last_days: reader.take(4) as u32,
+            last_weekday: flags & LAST_WEEKDAY != 0,
+            nearest_weekdays: reader.take(4) as u32,
+            last_of_week: reader.take(1) as u8,
+            nth_of_week: [0; 5].map(|_| reader.take(1) as u8),
+            timezone: match flags & TIMEZONE {
+                0 => None,
+                _ => {
+                    let timezone = bytes[reader.offset..]
+                        .try_into()
+                        .ok()
+                        .and_then(Tz::from_bytes);
+                    Some(timezone.ok_or_else(|| invalid("invalid timezone"))?)
+                }
+            },
+        };
+
+        let fits = |value: u64, min: Ordinal, max: Ordinal| {
+            value & !(((1u128 << (max + 1)) - (1u128 << min)) as u64) == 0
+        };
+        let is_valid = flags & !(DAYS_UNION | LAST_WEEKDAY | TIMEZONE) == 0
+            && (flags & TIMEZONE != 0 || bytes[reader.offset..].iter().all(|byte| *byte == 0))
+            && fits(compiled.seconds, 0, 59)
+            && fits(compiled.minutes, 0, 59)
+            && fits(compiled.hours.into(), 0, 23)
+            && fits(compiled.days_of_month.into(), 1, 31)
+            && fits(compiled.months.into(), 1, 12)
+            && fits(compiled.days_of_week.into(), 1, 7)
+            && fits(compiled.years[2], 0, YEARS - 128 - 1)
+            && fits(compiled.last_days.into(), 0, 30)
+            && fits(compiled.nearest_weekdays.into(), 1, 31)
+            && fits(compiled.last_of_week.into(), 1, 7)
+            && compiled
+                .nth_of_week
+                .iter()
+                .all(|days_of_week| fits((*days_of_week).into(), 1, 7));
+        match is_valid {
+            true => Ok(compiled),
+            false => Err(invalid("value out of range")),
+        }
+    }
+
+    /// Returns the timezone the schedule is evaluated in, if it's not the timezone of the queried moments.
+    pub fn timezone(&self) -> Option<Tz> {
+        self.timezone
+    }
+
+    /// Returns the first moment of the schedule after `after`, like `Schedule::next_after`.
+    pub fn next_after<Z>(&self, after: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        match self.timezone {
+            Some(timezone) => self
+                .next_after_local(&after.with_timezone(&timezone))
+                .map(|datetime| datetime.with_timezone(&after.timezone())),
+            None => self.next_after_local(after),
+        }
+    }
+
+    fn next_after_local<Z>(&self, after: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        // Moments are whole seconds.
+        let mut from = after.naive_local().with_nanosecond(0)? + Duration::seconds(1);
+        loop {
+            let local = self.next_local(&from)?;
+            // Repeated local times fire at their first occurrence after the reference.
+            let candidate = match resolve_local(&after.timezone(), &local) {
+                LocalResult::Single(candidate) => Some(candidate),
+                LocalResult::Ambiguous(earliest, latest) => {
+                    Some(if earliest.gt(after) { earliest } else { latest })
+                }
+                LocalResult::None => None,
+            };
+            if let Some(candidate) = candidate.filter(|candidate| candidate.gt(after)) {
+                return Some(candidate);
+            }
+            from = local + Duration::seconds(1);
+        }
+    }
+
+    /// Provides an iterator over the moments of the schedule after `after`.
+    pub fn after<'a, Z>(&'a self, after: &DateTime<Z>) -> impl Iterator<Item = DateTime<Z>> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        std::iter::successors(self.next_after(after), move |moment| {
+            self.next_after(moment)
+        })
+    }
+
+    /// Returns the first local time of the schedule at or after `from`.
+    fn next_local(&self, from: &NaiveDateTime) -> Option<NaiveDateTime> {
+        const MINIMUMS: [Ordinal; 6] = [0, 1, 1, 0, 0, 0];
+        let mut moment = [
+            from.year() as Ordinal,
+            from.month(),
+            from.day(),
+            from.hour(),
+            from.minute(),
+            from.second(),
+        ];
+
+        // Find the next included value of each unit, from years to seconds, carrying over when a unit runs out.
+        let mut unit = 0;
+        while unit < moment.len() {
+            let next = match unit {
+                0 => self.next_year(moment[0]),
+                1 => next_bit(self.months.into(), moment[1]),
+                2 => next_bit(self.days_in(moment[0], moment[1]).into(), moment[2]),
+                3 => next_bit(self.hours.into(), moment[3]),
+                4 => next_bit(self.minutes, moment[4]),
+                _ => next_bit(self.seconds, moment[5]),
+            };
+            match next {
+                Some(value) => {
+                    if value != moment[unit] {
+                        moment[unit] = value;
+                        moment[unit + 1..].copy_from_slice(&MINIMUMS[unit + 1..]);
+                    }
+                    unit += 1;
+                }
+                None if unit == 0 => return None,
+                None => {
+                    unit -= 1;
+                    moment[unit] += 1;
+                    moment[unit + 1..].copy_from_slice(&MINIMUMS[unit + 1..]);
+                }
+            }
+        }
+
+        let [year, month, day_of_month, hour, minute, second] = moment;
+        Some(local_datetime(
+            year,
+            month,
+            day_of_month,
+            hour,
+            minute,
+            second,
+        ))
+    }
+
+    fn next_year(&self, year: Ordinal) -> Option<Ordinal> {
+        let index = year.checked_sub(Years::inclusive_min())?;
+        (index / 64..3)
+            .find_map(|word| {
+                let from = if word == index / 64 { index % 64 } else { 0 };
+                next_bit(self.years[word as usize], from).map(|bit| word * 64 + bit)
+            })
+            .map(|index| index + Years::inclusive_min())
+    }
+
+    /// Returns the days of a month matching the days of month and the days of week of the schedule, as a bitset.
+    fn days_in(&self, year: Ordinal, month: Ordinal) -> u32 {
+        let last = days_in_month(month, year);
+        let rule_days = |day_rules: &mut dyn Iterator<Item = DayRule>| {
+            day_rules
+                .filter_map(|day_rule| day_rule.day_of_month(year, month))
+                .fold(0, |days, day| days | 1 << day)
+        };
+
+        let mut days_of_month = self.days_of_month & ((1u64 << (last + 1)) - 2) as u32;
+        days_of_month |= rule_days(
+            &mut set_bits(self.last_days.into())
+                .map(DayRule::Last)
+                .chain(self.last_weekday.then_some(DayRule::LastWeekday))
+                .chain(set_bits(self.nearest_weekdays.into()).map(DayRule::NearestWeekday)),
+        );
+
+        let first = day_of_week(year, month, 1);
+        let mut days_of_week = 0;
+        for day_of_week in set_bits(self.days_of_week.into()) {
+            let mut day = 1 + (day_of_week + 7 - first) % 7;
+            while day <= last {
+                days_of_week |= 1 << day;
+                day += 7;
+            }
+        }
+        days_of_week |= rule_days(
+            &mut set_bits(self.last_of_week.into())
+                .map(DayRule::LastOfWeek)
+                .chain((1..=5).flat_map(|nth| {
+                    set_bits(self.nth_of_week[nth as usize - 1].into())
+                        .map(move |day| DayRule::NthOfWeek(day, nth))
+                })),
+        );
+
+        if self.days_union {
+            days_of_month | days_of_week
+        } else {
+            days_of_month & days_of_week
+        }
+    }
+}
+
+/// Appends little-endian integers to the binary form of a compiled schedule.
+struct Writer {
+    bytes: [u8; CompiledSchedule::LEN],
+    offset: usize,
+}
+
+impl Default for Writer {
+    fn default() -> Self {
+        Writer {
+            bytes: [0; CompiledSchedule::LEN],
+            offset: 0,
+        }
+    }
+}
+
+impl Writer {
+    fn put(&mut self, value: u64, len: usize) {
+        self.bytes[self.offset..self.offset + len].copy_from_slice(&value.to_le_bytes()[..len]);
+        self.offset += len;
+    }
+}
+
+/// Reads little-endian integers from the binary form of a compiled schedule.
+struct Reader<'a> {
+    bytes: &'a [u8; CompiledSchedule::LEN],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> u64 {
+        let mut value = [0; 8];
+        value[..len].copy_from_slice(&self.bytes[self.offset..self.offset + len]);
+        self.offset += len;
+        u64::from_le_bytes(value)
+    }
+}
+
+fn bits(ordinals: &OrdinalSet) -> u64 {
+    ordinals.iter().fold(0, |bits, ordinal| bits | 1 << ordinal)
+}
+
+/// Returns the lowest set bit of `bits` at or above `from`.
+fn next_bit(bits: u64, from: Ordinal) -> Option<Ordinal> {
+    let bits = bits.checked_shr(from)?.checked_shl(from)?;
+    (bits != 0).then(|| bits.trailing_zeros())
+}
+
+fn set_bits(bits: u64) -> impl Iterator<Item = Ordinal> {
+    (0..64).filter(move |bit| bits & 1 << bit != 0)
+}
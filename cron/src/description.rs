@@ -0,0 +1,256 @@
+use crate::ordinal::{Ordinal, OrdinalSet};
+use crate::parsing::TIMEZONE_PREFIXES;
+use crate::schedule::{Schedule, ScheduleFields};
+use crate::time_unit::*;
+
+/// The maximum number of times of day listed one by one, e.g. "At 09:30, 12:30 and 15:30".
+const MAX_LISTED_TIMES: usize = 8;
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const DAY_OF_WEEK_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const NTH_NAMES: [&str; 5] = ["first", "second", "third", "fourth", "fifth"];
+
+impl Schedule {
+    /// Describes the schedule in English, e.g. "At 09:30 on Monday, Wednesday and Friday in May through August".
+    ///
+    /// Also available as the alternate form of `Display`, e.g. `format!("{:#}", schedule)`.
+    pub fn describe(&self) -> String {
+        let fields = &self.fields;
+        let mut phrases = vec![describe_time(fields)];
+        phrases.extend(describe_days(fields));
+        phrases.extend(describe_months(&fields.months.ordinals()));
+        phrases.extend(describe_years(&fields.years.ordinals()));
+        if let Some(timezone) = self.timezone_name() {
+            phrases.push(format!("({})", timezone));
+        }
+
+        let description = phrases.join(" ");
+        let mut chars = description.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => description,
+        }
+    }
+
+    /// Returns the timezone of the expression as written, e.g. `Europe/Paris`.
+    fn timezone_name(&self) -> Option<&str> {
+        self.timezone?;
+        let rest = TIMEZONE_PREFIXES
+            .iter()
+            .find_map(|prefix| self.source.trim_start().strip_prefix(prefix))?;
+        rest.split_whitespace().next()
+    }
+}
+
+fn describe_time(fields: &ScheduleFields) -> String {
+    let seconds = fields.seconds.ordinals();
+    let minutes = fields.minutes.ordinals();
+    let hours = fields.hours.ordinals();
+
+    // A few times of day are listed one by one.
+    if seconds.len() == 1 && minutes.len() == 1 && hours.len() <= MAX_LISTED_TIMES {
+        let (second, minute) = (first(&seconds), first(&minutes));
+        let times: Vec<String> = hours
+            .iter()
+            .map(|hour| match second {
+                0 => format!("{:02}:{:02}", hour, minute),
+                _ => format!("{:02}:{:02}:{:02}", hour, minute, second),
+            })
+            .collect();
+        return format!("at {}", join(&times));
+    }
+
+    let on_the_minute = seconds == OrdinalSet::from([0]);
+    let on_the_hour = on_the_minute && minutes == OrdinalSet::from([0]);
+    let mut phrases = vec![];
+    if !on_the_minute {
+        phrases.push(describe_unit::<Seconds>(&seconds, "second"));
+    }
+    // Minutes go without saying when several seconds of every minute are already described.
+    let every_minute = fields.minutes.is_all() && seconds.len() > 1;
+    if !on_the_hour && !every_minute {
+        phrases.push(describe_unit::<Minutes>(&minutes, "minute"));
+    }
+    if on_the_hour {
+        phrases.push(match contiguous(&hours) {
+            Some((start, end)) if !fields.hours.is_all() => {
+                format!("every hour between {:02}:00 and {:02}:59", start, end)
+            }
+            _ => describe_unit::<Hours>(&hours, "hour"),
+        });
+    } else if !fields.hours.is_all() {
+        phrases.push(match contiguous(&hours) {
+            Some((start, end)) => format!("between {:02}:00 and {:02}:59", start, end),
+            None => format!("during {}", describe_unit::<Hours>(&hours, "hour")),
+        });
+    }
+    phrases.join(", ")
+}
+
+fn describe_days(fields: &ScheduleFields) -> Option<String> {
+    let days_of_month = &fields.days_of_month;
+    let days_of_week = &fields.days_of_week;
+
+    let mut month_phrases = vec![];
+    if !days_of_month.is_all() && days_of_month.count() > 0 {
+        let days = days_of_month.ordinals();
+        month_phrases.push(match contiguous(&days) {
+            Some((start, end)) => format!("on days {} through {} of the month", start, end),
+            None if days.len() == 1 => format!("on day {} of the month", first(&days)),
+            None => format!("on days {} of the month", join(&to_strings(&days))),
+        });
+    }
+    month_phrases.extend(days_of_month.day_rules().iter().map(describe_day_rule));
+
+    let mut week_phrases = vec![];
+    if !days_of_week.is_all() && days_of_week.count() > 0 {
+        let days = days_of_week.ordinals();
+        let name = |day: &Ordinal| DAY_OF_WEEK_NAMES[*day as usize - 1].to_owned();
+        week_phrases.push(match contiguous(&days) {
+            Some((start, end)) if end - start > 1 => {
+                format!("on {} through {}", name(&start), name(&end))
+            }
+            _ => format!("on {}", join(&days.iter().map(name).collect::<Vec<_>>())),
+        });
+    }
+    week_phrases.extend(days_of_week.day_rules().iter().map(describe_day_rule));
+
+    let month_phrase = (!month_phrases.is_empty()).then(|| join_with(&month_phrases, "or"));
+    let week_phrase = (!week_phrases.is_empty()).then(|| join_with(&week_phrases, "or"));
+    match (month_phrase, week_phrase) {
+        (Some(month_phrase), Some(week_phrase)) if fields.days_union => {
+            Some(format!("{} or {}", month_phrase, week_phrase))
+        }
+        (Some(month_phrase), Some(week_phrase)) => {
+            Some(format!("{}, if it is also {}", month_phrase, week_phrase))
+        }
+        (month_phrase, week_phrase) => month_phrase.or(week_phrase),
+    }
+}
+
+fn describe_day_rule(day_rule: &DayRule) -> String {
+    match *day_rule {
+        DayRule::Last(0) => "on the last day of the month".to_owned(),
+        DayRule::Last(1) => "on the day before the last day of the month".to_owned(),
+        DayRule::Last(offset) => format!(
+            "on the day {} days before the last day of the month",
+            offset
+        ),
+        DayRule::LastWeekday => "on the last weekday of the month".to_owned(),
+        DayRule::NearestWeekday(day) => {
+            format!("on the weekday nearest to day {} of the month", day)
+        }
+        DayRule::LastOfWeek(day) => format!(
+            "on the last {} of the month",
+            DAY_OF_WEEK_NAMES[day as usize - 1]
+        ),
+        DayRule::NthOfWeek(day, nth) => format!(
+            "on the {} {} of the month",
+            NTH_NAMES[nth as usize - 1],
+            DAY_OF_WEEK_NAMES[day as usize - 1]
+        ),
+    }
+}
+
+fn describe_months(months: &OrdinalSet) -> Option<String> {
+    if months.len() == 12 {
+        return None;
+    }
+    let name = |month: &Ordinal| MONTH_NAMES[*month as usize - 1].to_owned();
+    Some(match contiguous(months) {
+        Some((start, end)) if end - start > 1 => {
+            format!("in {} through {}", name(&start), name(&end))
+        }
+        _ => format!("in {}", join(&months.iter().map(name).collect::<Vec<_>>())),
+    })
+}
+
+fn describe_years(years: &OrdinalSet) -> Option<String> {
+    if years.len() as Ordinal == Years::inclusive_max() - Years::inclusive_min() + 1 {
+        return None;
+    }
+    Some(match contiguous(years) {
+        Some((start, end)) if end > start => format!("in {} through {}", start, end),
+        _ => format!("in {}", join(&to_strings(years))),
+    })
+}
+
+/// Describes the ordinals of a unit of time, e.g. "every 5 minutes" or "at minutes 0 and 30".
+fn describe_unit<T: TimeUnitField>(ordinals: &OrdinalSet, unit: &str) -> String {
+    let (min, max) = (T::inclusive_min(), T::inclusive_max());
+    if ordinals.len() as Ordinal == max - min + 1 {
+        return format!("every {}", unit);
+    }
+    if ordinals.len() == 1 {
+        return format!("at {} {}", unit, first(ordinals));
+    }
+    if let Some((start, end)) = contiguous(ordinals) {
+        return format!("{}s {} through {}", unit, start, end);
+    }
+    if let Some(step) = step(ordinals) {
+        let (start, end) = (first(ordinals), *ordinals.iter().next_back().unwrap());
+        return match start == min && end + step > max {
+            true => format!("every {} {}s", step, unit),
+            false => format!("every {} {}s from {} through {}", step, unit, start, end),
+        };
+    }
+    format!("at {}s {}", unit, join(&to_strings(ordinals)))
+}
+
+fn first(ordinals: &OrdinalSet) -> Ordinal {
+    *ordinals.iter().next().unwrap()
+}
+
+/// Returns the bounds of ordinals forming a range of at least two ordinals.
+fn contiguous(ordinals: &OrdinalSet) -> Option<(Ordinal, Ordinal)> {
+    let (start, end) = (*ordinals.iter().next()?, *ordinals.iter().next_back()?);
+    (ordinals.len() > 1 && (end - start + 1) as usize == ordinals.len()).then_some((start, end))
+}
+
+/// Returns the step between three or more ordinals evenly spread by more than one.
+fn step(ordinals: &OrdinalSet) -> Option<Ordinal> {
+    let ordinals: Vec<Ordinal> = ordinals.iter().cloned().collect();
+    let step = ordinals.get(1)? - ordinals[0];
+    (ordinals.len() > 2 && step > 1 && ordinals.windows(2).all(|pair| pair[1] - pair[0] == step))
+        .then_some(step)
+}
+
+fn to_strings(ordinals: &OrdinalSet) -> Vec<String> {
+    ordinals.iter().map(ToString::to_string).collect()
+}
+
+fn join(items: &[String]) -> String {
+    join_with(items, "and")
+}
+
+/// Joins items as in "a, b and c".
+fn join_with(items: &[String], conjunction: &str) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [init @ .., last] => format!("{} {} {}", init.join(", "), conjunction, last),
+    }
+}
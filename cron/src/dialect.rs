@@ -0,0 +1,60 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+use crate::error::{Error, ErrorKind};
+
+/// The dialects of cron expressions, told apart by their number of fields.
+///
+/// Shorthands such as `@daily` or `@every 5m` don't belong to a dialect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dialect {
+    /// `min hour day-of-month month day-of-week`, as in Unix crontabs.
+    ///
+    /// Like in Unix crontabs, days of the week are numbered from 0 (Sunday) to 7 (Sunday), and if neither day
+    /// field starts with `*`, moments match when either day field matches.
+    Unix,
+
+    /// `sec min hour day-of-month month day-of-week`
+    Seconds,
+
+    /// `sec min hour day-of-month month day-of-week year`
+    Years,
+}
+
+impl Dialect {
+    /// Returns the number of fields of the dialect.
+    pub fn fields(&self) -> usize {
+        match self {
+            Dialect::Unix => 5,
+            Dialect::Seconds => 6,
+            Dialect::Years => 7,
+        }
+    }
+}
+
+impl Display for Dialect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Dialect::Unix => write!(f, "unix"),
+            Dialect::Seconds => write!(f, "seconds"),
+            Dialect::Years => write!(f, "years"),
+        }
+    }
+}
+
+impl FromStr for Dialect {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "unix" => Ok(Dialect::Unix),
+            "seconds" => Ok(Dialect::Seconds),
+            "years" => Ok(Dialect::Years),
+            _ => Err(ErrorKind::Expression(format!(
+                "'{}' is not a dialect. Expected 'unix', 'seconds' or 'years'.",
+                s
+            ))
+            .into()),
+        }
+    }
+}
@@ -1,19 +1,70 @@
+use std::borrow::Cow;
 use std::{error, fmt};
 
+use crate::ordinal::Ordinal;
+
 #[derive(Debug)]
 pub struct Error {
     kind: ErrorKind,
 }
 
+impl Error {
+    /// Returns what made the expression invalid.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// Returns the invalid token of the expression, if the error points at one.
+    pub fn token(&self) -> Option<&TokenError> {
+        match self.kind {
+            ErrorKind::Token(ref token) => Some(token),
+            ErrorKind::Expression(_) | ErrorKind::Compiled(_) => None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum ErrorKind {
     Expression(String),
+    Token(TokenError),
+    /// The binary form of a compiled schedule is invalid.
+    Compiled(String),
+}
+
+/// An invalid token of an expression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenError {
+    /// The name of the field the token belongs to, e.g. `Hours`, if any.
+    pub field: Option<Cow<'static, str>>,
+    /// The byte offset of the token in the expression.
+    pub offset: usize,
+    /// The invalid token.
+    pub token: String,
+    /// The inclusive range of values allowed in the field, if the token holds a value outside of it.
+    pub range: Option<(Ordinal, Ordinal)>,
+    /// Why the token is invalid.
+    pub message: String,
+}
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref field) = self.field {
+            write!(f, "{}: ", field.to_lowercase())?;
+        }
+        write!(
+            f,
+            "{} (at byte {}, '{}')",
+            self.message, self.offset, self.token
+        )
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.kind {
             ErrorKind::Expression(ref expr) => write!(f, "Invalid expression: {}", expr),
+            ErrorKind::Token(ref token) => write!(f, "Invalid expression: {}", token),
+            ErrorKind::Compiled(ref reason) => write!(f, "Invalid compiled schedule: {}", reason),
         }
     }
 }
@@ -25,3 +76,9 @@ impl From<ErrorKind> for Error {
         Error { kind }
     }
 }
+
+impl From<TokenError> for Error {
+    fn from(token: TokenError) -> Error {
+        ErrorKind::Token(token).into()
+    }
+}
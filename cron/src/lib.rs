@@ -38,6 +38,10 @@
 //! */
 //! ```
 
+mod calendar;
+mod compiled;
+mod description;
+mod dialect;
 pub mod error;
 mod ordinal;
 mod parsing;
@@ -45,6 +49,11 @@ mod queries;
 mod schedule;
 mod specifier;
 mod time_unit;
+mod timezone;
 
+pub use crate::calendar::{Calendar, Dates};
+pub use crate::compiled::CompiledSchedule;
+pub use crate::dialect::Dialect;
 pub use crate::schedule::Schedule;
 pub use crate::time_unit::TimeUnitSpec;
+pub use crate::timezone::{Tz, TzOffset};
@@ -1,31 +1,203 @@
 use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::{alpha1, digit1, multispace0};
-use nom::combinator::{all_consuming, eof, map, map_res, opt};
-use nom::multi::separated_list1;
-use nom::sequence::{delimited, separated_pair, terminated, tuple};
+use nom::bytes::complete::{tag, tag_no_case, take_while1};
+use nom::character::complete::{alpha1, digit1, multispace0, multispace1};
+use nom::combinator::{all_consuming, eof, map, map_opt, map_res, opt};
+use nom::multi::{many1, separated_list1};
+use nom::sequence::{delimited, preceded, separated_pair, terminated, tuple};
 use nom::IResult;
 
+use std::borrow::Cow;
 use std::convert::TryFrom;
 use std::str::{self, FromStr};
 
-use crate::error::{Error, ErrorKind};
+use crate::dialect::Dialect;
+use crate::error::{Error, ErrorKind, TokenError};
 use crate::ordinal::*;
 use crate::schedule::{Schedule, ScheduleFields};
 use crate::specifier::*;
 use crate::time_unit::*;
+use crate::timezone::Tz;
+
+/// The prefixes setting the timezone of an expression, e.g. `CRON_TZ=Europe/Paris 0 0 9 * * *`.
+pub(crate) const TIMEZONE_PREFIXES: [&str; 2] = ["CRON_TZ=", "TZ="];
 
 impl FromStr for Schedule {
     type Err = Error;
     fn from_str(expression: &str) -> Result<Self, Self::Err> {
-        match schedule(expression) {
-            Ok((_, schedule_fields)) => {
-                Ok(Schedule::new(String::from(expression), schedule_fields))
-            } // Extract from nom tuple
-            Err(_) => Err(ErrorKind::Expression("Invalid cron expression.".to_owned()).into()), //TODO: Details
+        Schedule::parse_seeded(expression, &[])
+    }
+}
+
+impl Schedule {
+    /// Parses an expression whose 'H' tokens hash to values derived from `seed`, e.g. the pubkey of a thread,
+    /// so that schedules sharing an expression spread their moments. Without a seed, 'H' tokens hash an empty seed.
+    pub fn parse_seeded(expression: &str, seed: &[u8]) -> Result<Schedule, Error> {
+        let (timezone, fields) = match TIMEZONE_PREFIXES
+            .iter()
+            .find_map(|prefix| expression.trim_start().strip_prefix(prefix))
+        {
+            Some(rest) => {
+                let (timezone, fields) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                let timezone = Tz::from_str(timezone).map_err(|error| {
+                    token_error(expression, timezone, Some(Cow::from("Timezone")), error)
+                })?;
+                (Some(timezone), fields)
+            }
+            None => (None, expression),
+        };
+        match seeded_schedule(fields, seed) {
+            Ok((_, (dialect, schedule_fields))) => Ok(Schedule::new(
+                String::from(expression),
+                schedule_fields,
+                timezone,
+                dialect,
+            )), // Extract from nom tuple
+            Err(_) => Err(diagnose(expression, fields)),
         }
     }
 }
+
+/// Explains why the fields of an expression don't parse, pointing at the first invalid token.
+/// Only called on failure, so the parsing of valid expressions doesn't pay for it.
+fn diagnose(expression: &str, fields: &str) -> Error {
+    let tokens: Vec<&str> = fields.split_whitespace().collect();
+    let error = match tokens.first() {
+        Some(shorthand) if shorthand.starts_with('@') => diagnose_shorthand(expression, &tokens),
+        _ => diagnose_longhand(expression, &tokens),
+    };
+    error.unwrap_or_else(|| ErrorKind::Expression("Invalid cron expression.".to_owned()).into())
+}
+
+fn diagnose_shorthand(expression: &str, tokens: &[&str]) -> Option<Error> {
+    let invalid = |token: &str, message: String| {
+        Some(token_error(
+            expression,
+            token,
+            None,
+            ErrorKind::Expression(message).into(),
+        ))
+    };
+    match tokens {
+        ["@yearly" | "@annually" | "@monthly" | "@weekly" | "@daily" | "@midnight" | "@hourly", unexpected, ..] => {
+            invalid(unexpected, "unexpected token after a shorthand".to_owned())
+        }
+        ["@every"] => invalid(tokens[0], "expected an interval, e.g. '@every 5m'".to_owned()),
+        ["@every", interval, rest @ ..] => match all_consuming(duration)(interval) {
+            Err(_) => invalid(
+                interval,
+                "invalid interval, expected a sequence of numbers of seconds (s), minutes (m), hours (h) or days (d)"
+                    .to_owned(),
+            ),
+            Ok((_, seconds)) if every_fields(seconds).is_none() => invalid(
+                interval,
+                "the interval must evenly divide a minute, an hour or a day".to_owned(),
+            ),
+            Ok(_) => rest
+                .first()
+                .and_then(|unexpected| invalid(unexpected, "unexpected token after a shorthand".to_owned())),
+        },
+        [shorthand, ..] => invalid(
+            shorthand,
+            "unknown shorthand, expected @yearly, @annually, @monthly, @weekly, @daily, @midnight, @hourly or @every"
+                .to_owned(),
+        ),
+        [] => None,
+    }
+}
+
+fn diagnose_longhand(expression: &str, tokens: &[&str]) -> Option<Error> {
+    let checks: &[fn(&str, &str) -> Option<Error>] = match tokens.len() {
+        0..=4 => {
+            return Some(
+                ErrorKind::Expression(format!(
+                    "Expected 5 (unix), 6 (seconds) or 7 (years) fields, found {}.",
+                    tokens.len()
+                ))
+                .into(),
+            )
+        }
+        5 => &[
+            diagnose_field::<Minutes>,
+            diagnose_field::<Hours>,
+            diagnose_field_with_any::<DaysOfMonth>,
+            diagnose_field::<Months>,
+            diagnose_field_with_any::<UnixDaysOfWeek>,
+        ],
+        _ => &[
+            diagnose_field::<Seconds>,
+            diagnose_field::<Minutes>,
+            diagnose_field::<Hours>,
+            diagnose_field_with_any::<DaysOfMonth>,
+            diagnose_field::<Months>,
+            diagnose_field_with_any::<DaysOfWeek>,
+            diagnose_field::<Years>,
+        ],
+    };
+    if let Some(unexpected) = tokens.get(checks.len()) {
+        return Some(token_error(
+            expression,
+            unexpected,
+            None,
+            ErrorKind::Expression("unexpected field, expressions have at most 7 fields".to_owned())
+                .into(),
+        ));
+    }
+    tokens
+        .iter()
+        .zip(checks)
+        .find_map(|(token, check)| check(expression, token))
+}
+
+fn diagnose_field<T: TimeUnitField>(expression: &str, field: &str) -> Option<Error> {
+    diagnose_items::<T>(expression, field, root_specifier)
+}
+
+fn diagnose_field_with_any<T: TimeUnitField>(expression: &str, field: &str) -> Option<Error> {
+    diagnose_items::<T>(expression, field, root_specifier_with_any)
+}
+
+fn diagnose_items<T: TimeUnitField>(
+    expression: &str,
+    field: &str,
+    root_specifier: fn(&str) -> IResult<&str, RootSpecifier>,
+) -> Option<Error> {
+    field.split(',').find_map(|item| {
+        let error = match all_consuming(root_specifier)(item) {
+            Ok((_, specifier)) => T::from_field(Field {
+                specifiers: vec![specifier],
+            })
+            .err()?,
+            Err(_) if item.is_empty() => ErrorKind::Expression("empty list item".to_owned()).into(),
+            Err(_) if item.contains('?') => {
+                ErrorKind::Expression("'?' is only allowed in the day fields".to_owned()).into()
+            }
+            Err(_) => ErrorKind::Expression("invalid syntax".to_owned()).into(),
+        };
+        Some(token_error(expression, item, Some(T::name()), error))
+    })
+}
+
+/// Points an error at a token of the expression, which must be a slice of it.
+fn token_error(
+    expression: &str,
+    token: &str,
+    field: Option<Cow<'static, str>>,
+    error: Error,
+) -> Error {
+    let offset = token.as_ptr() as usize - expression.as_ptr() as usize;
+    let (range, message) = match error.kind() {
+        ErrorKind::Token(error) => (error.range, error.message.clone()),
+        ErrorKind::Expression(message) | ErrorKind::Compiled(message) => (None, message.clone()),
+    };
+    TokenError {
+        field,
+        offset,
+        token: token.to_owned(),
+        range,
+        message,
+    }
+    .into()
+}
 impl TryFrom<&str> for Schedule {
     type Error = Error;
 
@@ -39,35 +211,64 @@ pub struct Field {
     pub specifiers: Vec<RootSpecifier>, // TODO: expose iterator?
 }
 
+impl Field {
+    /// Returns true if the field doesn't start with '*' (or '?'), as Unix crontabs tell restricted day fields apart.
+    fn is_restricted(&self) -> bool {
+        !matches!(
+            self.specifiers.first(),
+            Some(RootSpecifier::Specifier(Specifier::All))
+                | Some(RootSpecifier::Period(Specifier::All, _))
+        )
+    }
+}
+
 trait FromField
 where
     Self: Sized,
 {
     //TODO: Replace with std::convert::TryFrom when stable
-    fn from_field(field: Field) -> Result<Self, Error>;
+    fn from_field(field: Field) -> Result<Self, Error> {
+        Self::from_seeded_field(field, &[])
+    }
+
+    /// Converts a field whose 'H' tokens hash with `seed`.
+    fn from_seeded_field(field: Field, seed: &[u8]) -> Result<Self, Error>;
 }
 
 impl<T> FromField for T
 where
     T: TimeUnitField,
 {
-    fn from_field(field: Field) -> Result<T, Error> {
+    fn from_seeded_field(field: Field, seed: &[u8]) -> Result<T, Error> {
         if field.specifiers.len() == 1
             && field.specifiers.first().unwrap() == &RootSpecifier::from(Specifier::All)
         {
             return Ok(T::all());
         }
         let mut ordinals = OrdinalSet::new();
+        let mut day_rules = DayRuleSet::new();
         for specifier in field.specifiers {
+            let specifier = T::resolve_hashed(specifier, seed)?;
+            if let RootSpecifier::Specifier(specifier) = &specifier {
+                if specifier.is_day_rule() {
+                    day_rules.insert(T::day_rule_from_specifier(specifier)?);
+                    continue;
+                }
+            }
             let specifier_ordinals: OrdinalSet = T::ordinals_from_root_specifier(&specifier)?;
             for ordinal in specifier_ordinals {
                 ordinals.insert(T::validate_ordinal(ordinal)?);
             }
         }
-        Ok(T::from_ordinal_set(ordinals))
+        Ok(T::from_ordinal_set(ordinals).with_day_rules(day_rules))
     }
 }
 
+/// Converts fields into a unit of time, hashing their 'H' tokens with `seed`.
+fn seeded<T: TimeUnitField>(seed: &[u8]) -> impl Fn(Field) -> Result<T, Error> + '_ {
+    move |field| T::from_seeded_field(field, seed)
+}
+
 fn ordinal(i: &str) -> IResult<&str, u32> {
     map_res(delimited(multispace0, digit1, multispace0), u32::from_str)(i)
 }
@@ -116,6 +317,15 @@ fn named_range(i: &str) -> IResult<&str, Specifier> {
     })(i)
 }
 
+fn hashed(i: &str) -> IResult<&str, Specifier> {
+    let range = delimited(
+        tag("("),
+        separated_pair(ordinal, tag("-"), ordinal),
+        tag(")"),
+    );
+    map(preceded(tag("H"), opt(range)), Specifier::Hashed)(i)
+}
+
 fn all(i: &str) -> IResult<&str, Specifier> {
     let (i, _) = tag("*")(i)?;
     Ok((i, Specifier::All))
@@ -126,8 +336,75 @@ fn any(i: &str) -> IResult<&str, Specifier> {
     Ok((i, Specifier::All))
 }
 
+fn day_ordinal(i: &str) -> IResult<&str, u32> {
+    map_res(digit1, u32::from_str)(i)
+}
+
+fn day_name(i: &str) -> IResult<&str, String> {
+    map(alpha1, ToOwned::to_owned)(i)
+}
+
+fn last(i: &str) -> IResult<&str, Specifier> {
+    map(
+        preceded(tag_no_case("L"), opt(preceded(tag("-"), day_ordinal))),
+        |offset| Specifier::Last(offset.unwrap_or(0)),
+    )(i)
+}
+
+fn last_weekday(i: &str) -> IResult<&str, Specifier> {
+    let (i, _) = tag_no_case("LW")(i)?;
+    Ok((i, Specifier::LastWeekday))
+}
+
+fn nearest_weekday(i: &str) -> IResult<&str, Specifier> {
+    map(terminated(day_ordinal, tag_no_case("W")), |day| {
+        Specifier::NearestWeekday(day)
+    })(i)
+}
+
+fn last_of_week(i: &str) -> IResult<&str, Specifier> {
+    map(terminated(day_ordinal, tag_no_case("L")), |day| {
+        Specifier::LastOfWeek(day)
+    })(i)
+}
+
+fn named_last_of_week(i: &str) -> IResult<&str, Specifier> {
+    // Names of the days of the week don't contain an 'L', so it can only be the suffix.
+    let name = take_while1(|c: char| c.is_ascii_alphabetic() && !c.eq_ignore_ascii_case(&'l'));
+    map(terminated(name, tag_no_case("L")), |name: &str| {
+        Specifier::NamedLastOfWeek(name.to_owned())
+    })(i)
+}
+
+fn nth_of_week(i: &str) -> IResult<&str, Specifier> {
+    map(
+        separated_pair(day_ordinal, tag("#"), day_ordinal),
+        |(day, nth)| Specifier::NthOfWeek(day, nth),
+    )(i)
+}
+
+fn named_nth_of_week(i: &str) -> IResult<&str, Specifier> {
+    map(
+        separated_pair(day_name, tag("#"), day_ordinal),
+        |(name, nth)| Specifier::NamedNthOfWeek(name, nth),
+    )(i)
+}
+
+fn day_specifier(i: &str) -> IResult<&str, Specifier> {
+    let day_specifiers = alt((
+        last_weekday,
+        last,
+        nearest_weekday,
+        last_of_week,
+        nth_of_week,
+        named_last_of_week,
+        named_nth_of_week,
+    ));
+    delimited(multispace0, day_specifiers, multispace0)(i)
+}
+
 fn specifier(i: &str) -> IResult<&str, Specifier> {
-    alt((all, range, point, named_range))(i)
+    alt((all, hashed, range, point, named_range))(i)
 }
 
 fn specifier_with_any(i: &str) -> IResult<&str, Specifier> {
@@ -141,6 +418,7 @@ fn root_specifier(i: &str) -> IResult<&str, RootSpecifier> {
 fn root_specifier_with_any(i: &str) -> IResult<&str, RootSpecifier> {
     alt((
         period_with_any,
+        map(day_specifier, RootSpecifier::from),
         map(specifier_with_any, RootSpecifier::from),
         named_point,
     ))(i)
@@ -169,7 +447,7 @@ fn field_with_any(i: &str) -> IResult<&str, Field> {
 }
 
 fn shorthand_yearly(i: &str) -> IResult<&str, ScheduleFields> {
-    let (i, _) = tag("@yearly")(i)?;
+    let (i, _) = alt((tag("@yearly"), tag("@annually")))(i)?;
     let fields = ScheduleFields::new(
         Seconds::from_ordinal(0),
         Minutes::from_ordinal(0),
@@ -211,7 +489,7 @@ fn shorthand_weekly(i: &str) -> IResult<&str, ScheduleFields> {
 }
 
 fn shorthand_daily(i: &str) -> IResult<&str, ScheduleFields> {
-    let (i, _) = tag("@daily")(i)?;
+    let (i, _) = alt((tag("@daily"), tag("@midnight")))(i)?;
     let fields = ScheduleFields::new(
         Seconds::from_ordinal(0),
         Minutes::from_ordinal(0),
@@ -238,6 +516,74 @@ fn shorthand_hourly(i: &str) -> IResult<&str, ScheduleFields> {
     Ok((i, fields))
 }
 
+/// Maps an interval to the fields of a schedule, if it evenly divides a minute, an hour or a day.
+fn every_fields(seconds: u64) -> Option<ScheduleFields> {
+    let step = |unit: u64, max: u64| {
+        (seconds % unit == 0 && max % (seconds / unit) == 0)
+            .then(|| RootSpecifier::Period(Specifier::All, (seconds / unit) as u32))
+    };
+    let (seconds, minutes, hours) = if seconds == 0 {
+        return None;
+    } else if seconds == 86400 {
+        (
+            Seconds::from_ordinal(0),
+            Minutes::from_ordinal(0),
+            Hours::from_ordinal(0),
+        )
+    } else if let Some(hours) = step(3600, 24) {
+        let hours = Hours::ordinals_from_root_specifier(&hours).ok()?;
+        (
+            Seconds::from_ordinal(0),
+            Minutes::from_ordinal(0),
+            Hours::from_ordinal_set(hours),
+        )
+    } else if let Some(minutes) = step(60, 60) {
+        let minutes = Minutes::ordinals_from_root_specifier(&minutes).ok()?;
+        (
+            Seconds::from_ordinal(0),
+            Minutes::from_ordinal_set(minutes),
+            Hours::all(),
+        )
+    } else {
+        let seconds = Seconds::ordinals_from_root_specifier(&step(1, 60)?).ok()?;
+        (
+            Seconds::from_ordinal_set(seconds),
+            Minutes::all(),
+            Hours::all(),
+        )
+    };
+    Some(ScheduleFields::new(
+        seconds,
+        minutes,
+        hours,
+        DaysOfMonth::all(),
+        Months::all(),
+        DaysOfWeek::all(),
+        Years::all(),
+    ))
+}
+
+fn duration(i: &str) -> IResult<&str, u64> {
+    let unit = alt((
+        map(tag("s"), |_| 1),
+        map(tag("m"), |_| 60),
+        map(tag("h"), |_| 3600),
+        map(tag("d"), |_| 86400),
+    ));
+    let part = map(
+        tuple((map_res(digit1, u64::from_str), unit)),
+        |(count, unit)| count.saturating_mul(unit),
+    );
+    map(many1(part), |parts| {
+        parts.into_iter().fold(0, u64::saturating_add)
+    })(i)
+}
+
+fn shorthand_every(i: &str) -> IResult<&str, ScheduleFields> {
+    let (i, _) = tag("@every")(i)?;
+    map_opt(preceded(multispace1, duration), every_fields)(i)
+}
+
 fn shorthand(i: &str) -> IResult<&str, ScheduleFields> {
     let keywords = alt((
         shorthand_yearly,
@@ -245,18 +591,20 @@ fn shorthand(i: &str) -> IResult<&str, ScheduleFields> {
         shorthand_weekly,
         shorthand_daily,
         shorthand_hourly,
+        shorthand_every,
     ));
     delimited(multispace0, keywords, multispace0)(i)
 }
 
-fn longhand(i: &str) -> IResult<&str, ScheduleFields> {
-    let seconds = map_res(field, Seconds::from_field);
-    let minutes = map_res(field, Minutes::from_field);
-    let hours = map_res(field, Hours::from_field);
-    let days_of_month = map_res(field_with_any, DaysOfMonth::from_field);
-    let months = map_res(field, Months::from_field);
-    let days_of_week = map_res(field_with_any, DaysOfWeek::from_field);
-    let years = opt(map_res(field, Years::from_field));
+/// Parses the `sec min hour day-of-month month day-of-week [year]` dialects.
+fn longhand<'a>(i: &'a str, seed: &[u8]) -> IResult<&'a str, (Dialect, ScheduleFields)> {
+    let seconds = map_res(field, seeded::<Seconds>(seed));
+    let minutes = map_res(field, seeded::<Minutes>(seed));
+    let hours = map_res(field, seeded::<Hours>(seed));
+    let days_of_month = map_res(field_with_any, seeded::<DaysOfMonth>(seed));
+    let months = map_res(field, seeded::<Months>(seed));
+    let days_of_week = map_res(field_with_any, seeded::<DaysOfWeek>(seed));
+    let years = opt(map_res(field, seeded::<Years>(seed)));
     let fields = tuple((
         seconds,
         minutes,
@@ -270,8 +618,12 @@ fn longhand(i: &str) -> IResult<&str, ScheduleFields> {
     map(
         terminated(fields, eof),
         |(seconds, minutes, hours, days_of_month, months, days_of_week, years)| {
+            let dialect = match years {
+                Some(_) => Dialect::Years,
+                None => Dialect::Seconds,
+            };
             let years = years.unwrap_or_else(Years::all);
-            ScheduleFields::new(
+            let fields = ScheduleFields::new(
                 seconds,
                 minutes,
                 hours,
@@ -279,13 +631,67 @@ fn longhand(i: &str) -> IResult<&str, ScheduleFields> {
                 months,
                 days_of_week,
                 years,
+            );
+            (dialect, fields)
+        },
+    )(i)
+}
+
+/// Parses the `min hour day-of-month month day-of-week` dialect of Unix crontabs.
+fn unix_longhand<'a>(i: &'a str, seed: &[u8]) -> IResult<&'a str, (Dialect, ScheduleFields)> {
+    let minutes = map_res(field, seeded::<Minutes>(seed));
+    let hours = map_res(field, seeded::<Hours>(seed));
+    let days_of_month = map_res(field_with_any, move |field: Field| {
+        let is_restricted = field.is_restricted();
+        DaysOfMonth::from_seeded_field(field, seed)
+            .map(|days_of_month| (days_of_month, is_restricted))
+    });
+    let months = map_res(field, seeded::<Months>(seed));
+    let days_of_week = map_res(field_with_any, move |field: Field| {
+        let is_restricted = field.is_restricted();
+        UnixDaysOfWeek::from_seeded_field(field, seed)
+            .map(|days_of_week| (days_of_week, is_restricted))
+    });
+    let fields = tuple((minutes, hours, days_of_month, months, days_of_week));
+
+    map(
+        terminated(fields, eof),
+        |(
+            minutes,
+            hours,
+            (days_of_month, days_of_month_restricted),
+            months,
+            (days_of_week, days_of_week_restricted),
+        )| {
+            let fields = ScheduleFields::new(
+                Seconds::from_ordinal(0),
+                minutes,
+                hours,
+                days_of_month,
+                months,
+                days_of_week.into(),
+                Years::all(),
             )
+            .with_days_union(days_of_month_restricted && days_of_week_restricted);
+            (Dialect::Unix, fields)
         },
     )(i)
 }
 
-fn schedule(i: &str) -> IResult<&str, ScheduleFields> {
-    all_consuming(alt((shorthand, longhand)))(i)
+fn seeded_schedule<'a>(
+    i: &'a str,
+    seed: &[u8],
+) -> IResult<&'a str, (Option<Dialect>, ScheduleFields)> {
+    let longhands = map(
+        alt((|i| longhand(i, seed), |i| unix_longhand(i, seed))),
+        |(dialect, fields)| (Some(dialect), fields),
+    );
+    all_consuming(alt((map(shorthand, |fields| (None, fields)), longhands)))(i)
+}
+
+#[cfg(test)]
+fn schedule(i: &str) -> IResult<&str, (Option<Dialect>, ScheduleFields)> {
+    seeded_schedule(i, &[])
 }
 
 #[cfg(test)]
@@ -606,6 +1012,36 @@ mod test {
         assert!(schedule(expression).is_err());
     }
 
+    #[test]
+    fn test_nom_valid_day_specifiers() {
+        assert_eq!(day_specifier("L"), Ok(("", Specifier::Last(0))));
+        assert_eq!(day_specifier("L-3"), Ok(("", Specifier::Last(3))));
+        assert_eq!(day_specifier("LW"), Ok(("", Specifier::LastWeekday)));
+        assert_eq!(
+            day_specifier("15W"),
+            Ok(("", Specifier::NearestWeekday(15)))
+        );
+        assert_eq!(day_specifier("6L"), Ok(("", Specifier::LastOfWeek(6))));
+        assert_eq!(
+            day_specifier("FRIL"),
+            Ok(("", Specifier::NamedLastOfWeek("FRI".to_owned())))
+        );
+        assert_eq!(day_specifier("6#3"), Ok(("", Specifier::NthOfWeek(6, 3))));
+        assert_eq!(
+            day_specifier("FRI#3"),
+            Ok(("", Specifier::NamedNthOfWeek("FRI".to_owned(), 3)))
+        );
+    }
+
+    #[test]
+    fn test_nom_day_specifiers_only_in_day_fields() {
+        let expression = "0 0 0 LW * FRI#3";
+        schedule(expression).unwrap();
+
+        let expression = "0 0 0 * * * 2024L";
+        assert!(schedule(expression).is_err());
+    }
+
     /// Issue #86
     #[test]
     fn shorthand_must_match_whole_input() {
@@ -614,4 +1050,19 @@ mod test {
         let expression = " @dailyBla ";
         assert!(schedule(expression).is_err());
     }
+
+    #[test]
+    fn test_nom_valid_hashed() {
+        assert_eq!(hashed("H").unwrap(), ("", Specifier::Hashed(None)));
+        assert_eq!(
+            hashed("H(0-29)").unwrap(),
+            ("", Specifier::Hashed(Some((0, 29))))
+        );
+        assert_eq!(
+            root_specifier("H/15").unwrap(),
+            ("", RootSpecifier::Period(Specifier::Hashed(None), 15))
+        );
+        assert!(hashed("H(0)").unwrap().0 == "(0)");
+        assert!(hashed("h").is_err());
+    }
 }
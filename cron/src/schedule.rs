@@ -1,11 +1,15 @@
-use chrono::offset::TimeZone;
-use chrono::{DateTime, Datelike, Timelike};
+use chrono::offset::{LocalResult, Offset, TimeZone};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::iter::Rev;
 use std::ops::Bound::{Included, Unbounded};
 
+use crate::dialect::Dialect;
+use crate::error::{Error, ErrorKind};
 use crate::ordinal::*;
 use crate::queries::*;
 use crate::time_unit::*;
+use crate::timezone::Tz;
 
 impl From<Schedule> for String {
     fn from(schedule: Schedule) -> String {
@@ -15,16 +19,82 @@ impl From<Schedule> for String {
 
 #[derive(Clone, Debug, Eq)]
 pub struct Schedule {
-    source: String,
-    fields: ScheduleFields,
+    pub(crate) source: String,
+    pub(crate) fields: ScheduleFields,
+    pub(crate) timezone: Option<Tz>,
+    dialect: Option<Dialect>,
 }
 
 impl Schedule {
-    pub(crate) fn new(source: String, fields: ScheduleFields) -> Schedule {
-        Schedule { source, fields }
+    pub(crate) fn new(
+        source: String,
+        fields: ScheduleFields,
+        timezone: Option<Tz>,
+        dialect: Option<Dialect>,
+    ) -> Schedule {
+        Schedule {
+            source,
+            fields,
+            timezone,
+            dialect,
+        }
+    }
+
+    /// Parses an expression, requiring it to be written in the given dialect unless it's a shorthand.
+    pub fn parse_as(expression: &str, dialect: Dialect) -> Result<Schedule, Error> {
+        Schedule::parse_as_seeded(expression, dialect, &[])
+    }
+
+    /// Like `parse_as`, with the 'H' tokens of the expression hashed with `seed`, see `parse_seeded`.
+    pub fn parse_as_seeded(
+        expression: &str,
+        dialect: Dialect,
+        seed: &[u8],
+    ) -> Result<Schedule, Error> {
+        let schedule = Schedule::parse_seeded(expression, seed)?;
+        match schedule.dialect {
+            Some(found) if found != dialect => Err(ErrorKind::Expression(format!(
+                "Expected a {} expression with {} fields, found a {} expression with {} fields.",
+                dialect,
+                dialect.fields(),
+                found,
+                found.fields()
+            ))
+            .into()),
+            _ => Ok(schedule),
+        }
+    }
+
+    /// Returns the dialect of the expression, or `None` for shorthands.
+    pub fn dialect(&self) -> Option<Dialect> {
+        self.dialect
+    }
+
+    /// Returns the timezone the schedule is evaluated in, if it's not the timezone of the queried moments.
+    pub fn timezone(&self) -> Option<Tz> {
+        self.timezone
     }
 
+    /// Returns the first moment of the schedule after `after`.
+    ///
+    /// Schedules with a timezone are evaluated in their timezone:
+    /// - moments skipped by a forward transition (e.g. the start of daylight saving time) are shifted forward by the
+    ///   length of the transition, so a daily 02:30 schedule fires at 03:30 on the day the clocks skip from 02:00 to 03:00;
+    /// - moments repeated by a backward transition (e.g. the end of daylight saving time) fire once, at their first
+    ///   occurrence.
     pub fn next_after<Z>(&self, after: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        match self.timezone {
+            Some(timezone) => self
+                .next_after_local(&after.with_timezone(&timezone))
+                .map(|datetime| datetime.with_timezone(&after.timezone())),
+            None => self.next_after_local(after),
+        }
+    }
+
+    fn next_after_local<Z>(&self, after: &DateTime<Z>) -> Option<DateTime<Z>>
     where
         Z: TimeZone,
     {
@@ -42,25 +112,15 @@ impl Schedule {
             }
             let month_range = (Included(month_start), Included(Months::inclusive_max()));
             for month in self.fields.months.ordinals().range(month_range).cloned() {
+                let days_of_month = self.days_of_month_in(year, month);
                 let day_of_month_start = query.day_of_month_lower_bound();
-                if !self
-                    .fields
-                    .days_of_month
-                    .ordinals()
-                    .contains(&day_of_month_start)
-                {
+                if !days_of_month.contains(&day_of_month_start) {
                     query.reset_day_of_month();
                 }
                 let day_of_month_end = days_in_month(month, year);
                 let day_of_month_range = (Included(day_of_month_start), Included(day_of_month_end));
 
-                'day_loop: for day_of_month in self
-                    .fields
-                    .days_of_month
-                    .ordinals()
-                    .range(day_of_month_range)
-                    .cloned()
-                {
+                for day_of_month in days_of_month.range(day_of_month_range).cloned() {
                     let hour_start = query.hour_lower_bound();
                     if !self.fields.hours.ordinals().contains(&hour_start) {
                         query.reset_hour();
@@ -83,29 +143,24 @@ impl Schedule {
                             let second_range =
                                 (Included(second_start), Included(Seconds::inclusive_max()));
 
-                            if let Some(second) =
-                                self.fields.seconds.ordinals().range(second_range).next()
+                            for second in
+                                self.fields.seconds.ordinals().range(second_range).cloned()
                             {
-                                let timezone = after.timezone();
-                                let candidate = timezone
-                                    .with_ymd_and_hms(
-                                        year as i32,
-                                        month,
-                                        day_of_month,
-                                        hour,
-                                        minute,
-                                        *second,
-                                    )
-                                    .unwrap();
-                                if !self
-                                    .fields
-                                    .days_of_week
-                                    .ordinals()
-                                    .contains(&candidate.weekday().number_from_sunday())
+                                let local =
+                                    local_datetime(year, month, day_of_month, hour, minute, second);
+                                // Repeated local times fire at their first occurrence after the reference.
+                                let candidate = match resolve_local(&after.timezone(), &local) {
+                                    LocalResult::Single(candidate) => Some(candidate),
+                                    LocalResult::Ambiguous(earliest, latest) => {
+                                        Some(if earliest.gt(after) { earliest } else { latest })
+                                    }
+                                    LocalResult::None => None,
+                                };
+                                if let Some(candidate) =
+                                    candidate.filter(|candidate| candidate.gt(after))
                                 {
-                                    continue 'day_loop;
+                                    return Some(candidate);
                                 }
-                                return Some(candidate);
                             }
                             query.reset_minute();
                         } // End of minutes range
@@ -121,7 +176,21 @@ impl Schedule {
         None
     }
 
+    /// Returns the last moment of the schedule before `before`.
+    /// Schedules with a timezone are evaluated in their timezone, like in `next_after`.
     pub fn prev_before<Z>(&self, before: &DateTime<Z>) -> Option<DateTime<Z>>
+    where
+        Z: TimeZone,
+    {
+        match self.timezone {
+            Some(timezone) => self
+                .prev_before_local(&before.with_timezone(&timezone))
+                .map(|datetime| datetime.with_timezone(&before.timezone())),
+            None => self.prev_before_local(before),
+        }
+    }
+
+    fn prev_before_local<Z>(&self, before: &DateTime<Z>) -> Option<DateTime<Z>>
     where
         Z: TimeZone,
     {
@@ -149,13 +218,9 @@ impl Schedule {
                 .rev()
                 .cloned()
             {
+                let days_of_month = self.days_of_month_in(year, month);
                 let day_of_month_end = query.day_of_month_upper_bound();
-                if !self
-                    .fields
-                    .days_of_month
-                    .ordinals()
-                    .contains(&day_of_month_end)
-                {
+                if !days_of_month.contains(&day_of_month_end) {
                     query.reset_day_of_month();
                 }
 
@@ -166,14 +231,7 @@ impl Schedule {
                     Included(day_of_month_end),
                 );
 
-                'day_loop: for day_of_month in self
-                    .fields
-                    .days_of_month
-                    .ordinals()
-                    .range(day_of_month_range)
-                    .rev()
-                    .cloned()
-                {
+                for day_of_month in days_of_month.range(day_of_month_range).rev().cloned() {
                     let hour_start = query.hour_upper_bound();
                     if !self.fields.hours.ordinals().contains(&hour_start) {
                         query.reset_hour();
@@ -210,36 +268,33 @@ impl Schedule {
                             let second_range =
                                 (Included(Seconds::inclusive_min()), Included(second_start));
 
-                            //TODO Rework it
-                            if let Some(second) = self
+                            for second in self
                                 .fields
                                 .seconds
                                 .ordinals()
                                 .range(second_range)
                                 .rev()
                                 .cloned()
-                                .next()
                             {
-                                let timezone = before.timezone();
-                                let candidate = timezone
-                                    .with_ymd_and_hms(
-                                        year as i32,
-                                        month,
-                                        day_of_month,
-                                        hour,
-                                        minute,
-                                        second,
-                                    )
-                                    .unwrap();
-                                if !self
-                                    .fields
-                                    .days_of_week
-                                    .ordinals()
-                                    .contains(&candidate.weekday().number_from_sunday())
+                                let local =
+                                    local_datetime(year, month, day_of_month, hour, minute, second);
+                                // Repeated local times fire at their first occurrence, as in `next_after`.
+                                let candidate = match resolve_local(&before.timezone(), &local) {
+                                    LocalResult::Single(candidate) => Some(candidate),
+                                    LocalResult::Ambiguous(earliest, latest) => {
+                                        Some(if earliest.lt(before) {
+                                            earliest
+                                        } else {
+                                            latest
+                                        })
+                                    }
+                                    LocalResult::None => None,
+                                };
+                                if let Some(candidate) =
+                                    candidate.filter(|candidate| candidate.lt(before))
                                 {
-                                    continue 'day_loop;
+                                    return Some(candidate);
                                 }
-                                return Some(candidate);
                             }
                             query.reset_minute();
                         } // End of minutes range
@@ -272,25 +327,75 @@ impl Schedule {
         ScheduleIterator::new(self, after)
     }
 
+    /// Provides an iterator over the moments of the schedule before `before`, latest first.
+    pub fn before<Z>(&self, before: &DateTime<Z>) -> Rev<ScheduleIterator<'_, Z>>
+    where
+        Z: TimeZone,
+    {
+        ScheduleIterator::new(self, before).rev()
+    }
+
+    /// Provides an iterator over the moments of the schedule from `start`, included, to `end`, excluded,
+    /// so that consecutive periods don't share moments.
+    pub fn between<'a, Z>(
+        &'a self,
+        start: &DateTime<Z>,
+        end: &DateTime<Z>,
+    ) -> impl Iterator<Item = DateTime<Z>> + 'a
+    where
+        Z: TimeZone + 'a,
+    {
+        let (start, end) = (start.clone(), end.clone());
+        // Moments are whole seconds, so the first one at or after `start` is after the second before it.
+        self.after(&(start.clone() - Duration::seconds(1)))
+            .skip_while(move |moment| moment.lt(&start))
+            .take_while(move |moment| moment.lt(&end))
+    }
+
+    /// Returns the number of moments of the schedule from `start`, included, to `end`, excluded.
+    pub fn count_between<Z>(&self, start: &DateTime<Z>, end: &DateTime<Z>) -> usize
+    where
+        Z: TimeZone,
+    {
+        self.between(start, end).count()
+    }
+
+    /// Returns true if `date_time` is a moment of the schedule, ignoring its fractions of a second.
     pub fn includes<Z>(&self, date_time: DateTime<Z>) -> bool
+    where
+        Z: TimeZone,
+    {
+        match self.timezone {
+            Some(timezone) => self.includes_local(date_time.with_timezone(&timezone)),
+            None => self.includes_local(date_time),
+        }
+    }
+
+    fn includes_local<Z>(&self, date_time: DateTime<Z>) -> bool
     where
         Z: TimeZone,
     {
         self.fields.years.includes(date_time.year() as Ordinal)
             && self.fields.months.includes(date_time.month() as Ordinal)
             && self
-                .fields
-                .days_of_week
-                .includes(date_time.weekday().number_from_sunday())
-            && self
-                .fields
-                .days_of_month
-                .includes(date_time.day() as Ordinal)
+                .days_of_month_in(date_time.year() as Ordinal, date_time.month() as Ordinal)
+                .contains(&(date_time.day() as Ordinal))
             && self.fields.hours.includes(date_time.hour() as Ordinal)
             && self.fields.minutes.includes(date_time.minute() as Ordinal)
             && self.fields.seconds.includes(date_time.second() as Ordinal)
     }
 
+    /// Returns the days of a month matching the days of month and the days of week of the schedule.
+    fn days_of_month_in(&self, year: Ordinal, month: Ordinal) -> OrdinalSet {
+        let days_of_month = self.fields.days_of_month.resolve(year, month);
+        let days_of_week = self.fields.days_of_week.resolve(year, month);
+        if self.fields.days_union {
+            days_of_month.union(&days_of_week).cloned().collect()
+        } else {
+            days_of_month.intersection(&days_of_week).cloned().collect()
+        }
+    }
+
     /// Returns a [TimeUnitSpec](trait.TimeUnitSpec.html) describing the years included
     /// in this [Schedule](struct.Schedule.html).
     pub fn years(&self) -> &impl TimeUnitSpec {
@@ -338,9 +443,45 @@ impl Schedule {
     }
 }
 
+pub(crate) fn local_datetime(
+    year: Ordinal,
+    month: Ordinal,
+    day_of_month: Ordinal,
+    hour: Ordinal,
+    minute: Ordinal,
+    second: Ordinal,
+) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(year as i32, month, day_of_month)
+        .and_then(|date| date.and_hms_opt(hour, minute, second))
+        .unwrap()
+}
+
+/// Maps a local time to the moments it designates in a timezone.
+/// Local times skipped by a forward transition are shifted forward by the length of the transition.
+pub(crate) fn resolve_local<Z>(timezone: &Z, local: &NaiveDateTime) -> LocalResult<DateTime<Z>>
+where
+    Z: TimeZone,
+{
+    match timezone.from_local_datetime(local) {
+        LocalResult::None => {
+            // Interpret the local time with the offset in effect before the transition.
+            // Transitions are more than a day apart.
+            let offset = timezone
+                .offset_from_utc_datetime(&(*local - Duration::days(1)))
+                .fix();
+            LocalResult::Single(timezone.from_utc_datetime(&(*local - offset)))
+        }
+        result => result,
+    }
+}
+
+/// Displays the expression, or its description in the alternate form (`{:#}`).
 impl Display for Schedule {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "{}", self.source)
+        match f.alternate() {
+            true => write!(f, "{}", self.describe()),
+            false => write!(f, "{}", self.source),
+        }
     }
 }
 
@@ -352,13 +493,14 @@ impl PartialEq for Schedule {
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ScheduleFields {
-    years: Years,
-    days_of_week: DaysOfWeek,
-    months: Months,
-    days_of_month: DaysOfMonth,
-    hours: Hours,
-    minutes: Minutes,
-    seconds: Seconds,
+    pub(crate) years: Years,
+    pub(crate) days_of_week: DaysOfWeek,
+    pub(crate) months: Months,
+    pub(crate) days_of_month: DaysOfMonth,
+    pub(crate) hours: Hours,
+    pub(crate) minutes: Minutes,
+    pub(crate) seconds: Seconds,
+    pub(crate) days_union: bool,
 }
 
 impl ScheduleFields {
@@ -379,8 +521,14 @@ impl ScheduleFields {
             hours,
             minutes,
             seconds,
+            days_union: false,
         }
     }
+
+    /// Makes moments match when either the days of month or the days of week match, as in Unix crontabs.
+    pub(crate) fn with_days_union(self, days_union: bool) -> ScheduleFields {
+        ScheduleFields { days_union, ..self }
+    }
 }
 
 pub struct ScheduleIterator<'a, Z>
@@ -452,7 +600,7 @@ fn is_leap_year(year: Ordinal) -> bool {
     by_four && ((!by_hundred) || by_four_hundred)
 }
 
-fn days_in_month(month: Ordinal, year: Ordinal) -> u32 {
+pub(crate) fn days_in_month(month: Ordinal, year: Ordinal) -> u32 {
     let is_leap_year = is_leap_year(year);
     match month {
         9 | 4 | 6 | 11 => 30,
@@ -6,6 +6,31 @@ pub enum Specifier {
     Point(Ordinal),
     Range(Ordinal, Ordinal),
     NamedRange(String, String),
+    // Days resolved in each month, only supported by the day of month and day of week fields:
+    // - last: 'L', 'L-3'
+    // - last weekday: 'LW'
+    // - nearest weekday: '15W'
+    // - last day of the week: '6L', 'FRIL'
+    // - nth day of the week: '6#3', 'FRI#3'
+    Last(Ordinal),
+    LastWeekday,
+    NearestWeekday(Ordinal),
+    LastOfWeek(Ordinal),
+    NamedLastOfWeek(String),
+    NthOfWeek(Ordinal, Ordinal),
+    NamedNthOfWeek(String, Ordinal),
+    // A value hashed from the seed of the schedule, within the field or a range of it: 'H', 'H(0-29)'
+    Hashed(Option<(Ordinal, Ordinal)>),
+}
+
+impl Specifier {
+    /// Returns true if the specifier designates days resolved in each month.
+    pub fn is_day_rule(&self) -> bool {
+        !matches!(
+            self,
+            Self::All | Self::Point(_) | Self::Range(..) | Self::NamedRange(..) | Self::Hashed(_)
+        )
+    }
 }
 
 // Separating out a root specifier allows for a higher tiered specifier, allowing us to achieve
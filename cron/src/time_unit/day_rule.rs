@@ -0,0 +1,86 @@
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeSet;
+
+use crate::ordinal::Ordinal;
+use crate::schedule::days_in_month;
+
+pub type DayRuleSet = BTreeSet<DayRule>;
+
+/// A day whose day of month depends on the month, e.g. the last day of the month or its third Friday.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DayRule {
+    /// The last day of the month, minus an offset ('L', 'L-3').
+    Last(Ordinal),
+    /// The last weekday (Monday to Friday) of the month ('LW').
+    LastWeekday,
+    /// The weekday nearest to the given day of month, without leaving the month ('15W').
+    NearestWeekday(Ordinal),
+    /// The last given day of the week of the month ('6L', 'FRIL').
+    LastOfWeek(Ordinal),
+    /// The nth given day of the week of the month ('6#3', 'FRI#3').
+    NthOfWeek(Ordinal, Ordinal),
+}
+
+impl DayRule {
+    /// Returns the day of month the rule resolves to in the given month, if any.
+    pub fn day_of_month(&self, year: Ordinal, month: Ordinal) -> Option<Ordinal> {
+        let last = days_in_month(month, year);
+        match *self {
+            DayRule::Last(offset) => last.checked_sub(offset).filter(|day| *day >= 1),
+            DayRule::LastWeekday => match day_of_week(year, month, last) {
+                1 => Some(last - 2),
+                7 => Some(last - 1),
+                _ => Some(last),
+            },
+            DayRule::NearestWeekday(day) if day > last => None,
+            DayRule::NearestWeekday(day) => match day_of_week(year, month, day) {
+                1 if day == last => Some(day - 2),
+                1 => Some(day + 1),
+                7 if day == 1 => Some(day + 2),
+                7 => Some(day - 1),
+                _ => Some(day),
+            },
+            DayRule::LastOfWeek(day_of_week_ordinal) => {
+                Some(last - (day_of_week(year, month, last) + 7 - day_of_week_ordinal) % 7)
+            }
+            DayRule::NthOfWeek(day_of_week_ordinal, nth) => {
+                let first = 1 + (day_of_week_ordinal + 7 - day_of_week(year, month, 1)) % 7;
+                Some(first + 7 * (nth - 1)).filter(|day| *day <= last)
+            }
+        }
+    }
+}
+
+/// Returns the day of the week of a date, from 1 (Sunday) to 7 (Saturday).
+pub fn day_of_week(year: Ordinal, month: Ordinal, day_of_month: Ordinal) -> Ordinal {
+    NaiveDate::from_ymd_opt(year as i32, month, day_of_month)
+        .unwrap()
+        .weekday()
+        .number_from_sunday()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_day_of_month() {
+        // June 2024 starts on a Saturday and ends on a Sunday.
+        assert_eq!(DayRule::Last(0).day_of_month(2024, 6), Some(30));
+        assert_eq!(DayRule::Last(3).day_of_month(2024, 2), Some(26));
+        assert_eq!(DayRule::Last(30).day_of_month(2024, 2), None);
+        assert_eq!(DayRule::LastWeekday.day_of_month(2024, 6), Some(28));
+        assert_eq!(DayRule::LastWeekday.day_of_month(2024, 7), Some(31));
+        assert_eq!(DayRule::NearestWeekday(1).day_of_month(2024, 6), Some(3));
+        assert_eq!(DayRule::NearestWeekday(2).day_of_month(2024, 6), Some(3));
+        assert_eq!(DayRule::NearestWeekday(8).day_of_month(2024, 6), Some(7));
+        assert_eq!(DayRule::NearestWeekday(30).day_of_month(2024, 6), Some(28));
+        assert_eq!(DayRule::NearestWeekday(31).day_of_month(2024, 6), None);
+        assert_eq!(DayRule::LastOfWeek(6).day_of_month(2024, 6), Some(28));
+        assert_eq!(DayRule::LastOfWeek(1).day_of_month(2024, 6), Some(30));
+        assert_eq!(DayRule::NthOfWeek(7, 1).day_of_month(2024, 6), Some(1));
+        assert_eq!(DayRule::NthOfWeek(6, 3).day_of_month(2024, 6), Some(21));
+        assert_eq!(DayRule::NthOfWeek(1, 5).day_of_month(2024, 6), Some(30));
+        assert_eq!(DayRule::NthOfWeek(2, 5).day_of_month(2024, 6), None);
+    }
+}
@@ -1,18 +1,26 @@
+use crate::error::*;
 use crate::ordinal::{Ordinal, OrdinalSet};
-use crate::time_unit::TimeUnitField;
+use crate::schedule::days_in_month;
+use crate::specifier::Specifier;
+use crate::time_unit::{DayRule, DayRuleSet, TimeUnitField};
 use std::borrow::Cow;
 
 #[derive(Clone, Debug, Eq)]
 pub struct DaysOfMonth {
     ordinals: Option<OrdinalSet>,
+    day_rules: DayRuleSet,
 }
 
 impl TimeUnitField for DaysOfMonth {
     fn from_optional_ordinal_set(ordinal_set: Option<OrdinalSet>) -> Self {
         DaysOfMonth {
             ordinals: ordinal_set,
+            day_rules: DayRuleSet::new(),
         }
     }
+    fn with_day_rules(self, day_rules: DayRuleSet) -> Self {
+        DaysOfMonth { day_rules, ..self }
+    }
     fn name() -> Cow<'static, str> {
         Cow::from("Days of Month")
     }
@@ -22,6 +30,31 @@ impl TimeUnitField for DaysOfMonth {
     fn inclusive_max() -> Ordinal {
         31
     }
+    /// Days after the 28th are skipped in some months, so 'H' tokens don't hash to them.
+    fn hashed_range() -> (Ordinal, Ordinal) {
+        (1, 28)
+    }
+    fn day_rule_from_specifier(specifier: &Specifier) -> Result<DayRule, Error> {
+        match *specifier {
+            Specifier::Last(offset) if offset < Self::inclusive_max() => Ok(DayRule::Last(offset)),
+            Specifier::Last(offset) => Err(ErrorKind::Expression(format!(
+                "The offset from the last day of the month must be less than {}. ('{}' specified.)",
+                Self::inclusive_max(),
+                offset
+            ))
+            .into()),
+            Specifier::LastWeekday => Ok(DayRule::LastWeekday),
+            Specifier::NearestWeekday(day) => {
+                Ok(DayRule::NearestWeekday(Self::validate_ordinal(day)?))
+            }
+            _ => Err(ErrorKind::Expression(format!(
+                "The '{}' field does not support days of the week. '{:?}' specified.",
+                Self::name(),
+                specifier
+            ))
+            .into()),
+        }
+    }
     fn ordinals(&self) -> OrdinalSet {
         match self.ordinals.clone() {
             Some(ordinal_set) => ordinal_set,
@@ -30,8 +63,30 @@ impl TimeUnitField for DaysOfMonth {
     }
 }
 
+impl DaysOfMonth {
+    /// Returns the rules of days whose day of month depends on the month.
+    pub(crate) fn day_rules(&self) -> &DayRuleSet {
+        &self.day_rules
+    }
+
+    /// Returns the days of the given month matched by the field.
+    pub fn resolve(&self, year: Ordinal, month: Ordinal) -> OrdinalSet {
+        let mut days: OrdinalSet = self
+            .ordinals()
+            .range(..=days_in_month(month, year))
+            .cloned()
+            .collect();
+        days.extend(
+            self.day_rules
+                .iter()
+                .filter_map(|day_rule| day_rule.day_of_month(year, month)),
+        );
+        days
+    }
+}
+
 impl PartialEq for DaysOfMonth {
     fn eq(&self, other: &DaysOfMonth) -> bool {
-        self.ordinals() == other.ordinals()
+        self.ordinals() == other.ordinals() && self.day_rules == other.day_rules
     }
 }
@@ -1,19 +1,26 @@
 use crate::error::*;
 use crate::ordinal::{Ordinal, OrdinalSet};
-use crate::time_unit::TimeUnitField;
+use crate::schedule::days_in_month;
+use crate::specifier::Specifier;
+use crate::time_unit::{day_of_week, DayRule, DayRuleSet, TimeUnitField};
 use std::borrow::Cow;
 
 #[derive(Clone, Debug, Eq)]
 pub struct DaysOfWeek {
     ordinals: Option<OrdinalSet>,
+    day_rules: DayRuleSet,
 }
 
 impl TimeUnitField for DaysOfWeek {
     fn from_optional_ordinal_set(ordinal_set: Option<OrdinalSet>) -> Self {
         DaysOfWeek {
             ordinals: ordinal_set,
+            day_rules: DayRuleSet::new(),
         }
     }
+    fn with_day_rules(self, day_rules: DayRuleSet) -> Self {
+        DaysOfWeek { day_rules, ..self }
+    }
     fn name() -> Cow<'static, str> {
         Cow::from("Days of Week")
     }
@@ -43,6 +50,30 @@ impl TimeUnitField for DaysOfWeek {
         };
         Ok(ordinal)
     }
+    fn day_rule_from_specifier(specifier: &Specifier) -> Result<DayRule, Error> {
+        match *specifier {
+            Specifier::LastOfWeek(ordinal) => {
+                Ok(DayRule::LastOfWeek(Self::validate_ordinal(ordinal)?))
+            }
+            Specifier::NamedLastOfWeek(ref name) => {
+                Ok(DayRule::LastOfWeek(Self::ordinal_from_name(name)?))
+            }
+            Specifier::NthOfWeek(ordinal, nth) => Ok(DayRule::NthOfWeek(
+                Self::validate_ordinal(ordinal)?,
+                validate_nth(nth)?,
+            )),
+            Specifier::NamedNthOfWeek(ref name, nth) => Ok(DayRule::NthOfWeek(
+                Self::ordinal_from_name(name)?,
+                validate_nth(nth)?,
+            )),
+            _ => Err(ErrorKind::Expression(format!(
+                "The '{}' field only supports 'L' and '#' after a day of the week, e.g. 'FRIL' or 'FRI#3'. '{:?}' specified.",
+                Self::name(),
+                specifier
+            ))
+            .into()),
+        }
+    }
     fn ordinals(&self) -> OrdinalSet {
         match self.ordinals.clone() {
             Some(ordinal_set) => ordinal_set,
@@ -51,8 +82,106 @@ impl TimeUnitField for DaysOfWeek {
     }
 }
 
+impl DaysOfWeek {
+    /// Returns the rules of days whose day of month depends on the month.
+    pub(crate) fn day_rules(&self) -> &DayRuleSet {
+        &self.day_rules
+    }
+
+    /// Returns the days of the given month matched by the field.
+    pub fn resolve(&self, year: Ordinal, month: Ordinal) -> OrdinalSet {
+        let ordinals = self.ordinals();
+        let first = day_of_week(year, month, 1);
+        let mut days: OrdinalSet = (1..=days_in_month(month, year))
+            .filter(|day| ordinals.contains(&((first + day - 2) % 7 + 1)))
+            .collect();
+        days.extend(
+            self.day_rules
+                .iter()
+                .filter_map(|day_rule| day_rule.day_of_month(year, month)),
+        );
+        days
+    }
+}
+
 impl PartialEq for DaysOfWeek {
     fn eq(&self, other: &DaysOfWeek) -> bool {
-        self.ordinals() == other.ordinals()
+        self.ordinals() == other.ordinals() && self.day_rules == other.day_rules
+    }
+}
+
+fn validate_nth(nth: Ordinal) -> Result<Ordinal, Error> {
+    match nth {
+        1..=5 => Ok(nth),
+        _ => Err(ErrorKind::Expression(format!(
+            "The occurrence of a day of the week in a month must be between 1 and 5. ('{}' specified.)",
+            nth
+        ))
+        .into()),
+    }
+}
+
+/// Days of week numbered as in Unix crontabs, from 0 (Sunday) to 7 (Sunday).
+#[derive(Clone, Debug)]
+pub struct UnixDaysOfWeek {
+    ordinals: Option<OrdinalSet>,
+    day_rules: DayRuleSet,
+}
+
+impl TimeUnitField for UnixDaysOfWeek {
+    fn from_optional_ordinal_set(ordinal_set: Option<OrdinalSet>) -> Self {
+        UnixDaysOfWeek {
+            ordinals: ordinal_set,
+            day_rules: DayRuleSet::new(),
+        }
+    }
+    fn with_day_rules(self, day_rules: DayRuleSet) -> Self {
+        UnixDaysOfWeek { day_rules, ..self }
+    }
+    fn name() -> Cow<'static, str> {
+        DaysOfWeek::name()
+    }
+    fn inclusive_min() -> Ordinal {
+        0
+    }
+    fn inclusive_max() -> Ordinal {
+        7
+    }
+    fn ordinal_from_name(name: &str) -> Result<Ordinal, Error> {
+        DaysOfWeek::ordinal_from_name(name).map(|ordinal| ordinal - 1)
+    }
+    fn day_rule_from_specifier(specifier: &Specifier) -> Result<DayRule, Error> {
+        // Day rules are numbered like `DaysOfWeek`.
+        let specifier = match *specifier {
+            Specifier::LastOfWeek(ordinal) => {
+                Specifier::LastOfWeek(to_day_of_week(Self::validate_ordinal(ordinal)?))
+            }
+            Specifier::NthOfWeek(ordinal, nth) => {
+                Specifier::NthOfWeek(to_day_of_week(Self::validate_ordinal(ordinal)?), nth)
+            }
+            _ => return DaysOfWeek::day_rule_from_specifier(specifier),
+        };
+        DaysOfWeek::day_rule_from_specifier(&specifier)
     }
+    fn ordinals(&self) -> OrdinalSet {
+        match self.ordinals.clone() {
+            Some(ordinal_set) => ordinal_set,
+            None => UnixDaysOfWeek::supported_ordinals(),
+        }
+    }
+}
+
+impl From<UnixDaysOfWeek> for DaysOfWeek {
+    fn from(days_of_week: UnixDaysOfWeek) -> Self {
+        DaysOfWeek {
+            ordinals: days_of_week
+                .ordinals
+                .map(|ordinals| ordinals.into_iter().map(to_day_of_week).collect()),
+            day_rules: days_of_week.day_rules,
+        }
+    }
+}
+
+fn to_day_of_week(unix_ordinal: Ordinal) -> Ordinal {
+    unix_ordinal % 7 + 1
 }
@@ -1,3 +1,4 @@
+mod day_rule;
 mod days_of_month;
 mod days_of_week;
 mod hours;
@@ -6,8 +7,9 @@ mod months;
 mod seconds;
 mod years;
 
+pub use self::day_rule::{day_of_week, DayRule, DayRuleSet};
 pub use self::days_of_month::DaysOfMonth;
-pub use self::days_of_week::DaysOfWeek;
+pub use self::days_of_week::{DaysOfWeek, UnixDaysOfWeek};
 pub use self::hours::Hours;
 pub use self::minutes::Minutes;
 pub use self::months::Months;
@@ -136,23 +138,75 @@ where
         .into())
     }
 
+    /// Adds days resolved in each month to the field. Only fields accepting `day_rule_from_specifier` hold them.
+    fn with_day_rules(self, _day_rules: DayRuleSet) -> Self {
+        self
+    }
+
+    fn day_rule_from_specifier(specifier: &Specifier) -> Result<DayRule, Error> {
+        Err(ErrorKind::Expression(format!(
+            "The '{}' field does not support 'L', 'W' or '#'. '{:?}' specified.",
+            Self::name(),
+            specifier
+        ))
+        .into())
+    }
+
+    /// The range 'H' tokens hash into when they don't specify one.
+    fn hashed_range() -> (Ordinal, Ordinal) {
+        (Self::inclusive_min(), Self::inclusive_max())
+    }
+
+    /// Replaces an 'H' token with the value it hashes to with `seed`: 'H' becomes a point and 'H/n' a period
+    /// starting at an offset below `n`. The hash is salted with the name of the field, so that the fields of a
+    /// schedule get different values. Other specifiers are returned as is.
+    fn resolve_hashed(root_specifier: RootSpecifier, seed: &[u8]) -> Result<RootSpecifier, Error> {
+        let hashed_range = |range: Option<(Ordinal, Ordinal)>| match range {
+            None => Ok(Self::hashed_range()),
+            Some((start, end)) => {
+                match (Self::validate_ordinal(start)?, Self::validate_ordinal(end)?) {
+                    (start, end) if start <= end => Ok((start, end)),
+                    _ => Err(Error::from(ErrorKind::Expression(format!(
+                        "Invalid hashed range for {}: {}-{}",
+                        Self::name(),
+                        start,
+                        end
+                    )))),
+                }
+            }
+        };
+        let hash = seed_hash(seed, &Self::name());
+        let resolved = match root_specifier {
+            RootSpecifier::Specifier(Specifier::Hashed(range)) => {
+                let (start, end) = hashed_range(range)?;
+                let offset = hash % u64::from(end - start + 1);
+                RootSpecifier::from(Specifier::Point(start + offset as Ordinal))
+            }
+            RootSpecifier::Period(Specifier::Hashed(range), step) => {
+                let (start, end) = hashed_range(range)?;
+                let offset = hash % u64::from(step.clamp(1, end - start + 1));
+                RootSpecifier::Period(Specifier::Range(start + offset as Ordinal, end), step)
+            }
+            root_specifier => root_specifier,
+        };
+        Ok(resolved)
+    }
+
     fn validate_ordinal(ordinal: Ordinal) -> Result<Ordinal, Error> {
         //println!("validate_ordinal for {} => {}", Self::name(), ordinal);
         match ordinal {
-            i if i < Self::inclusive_min() => Err(ErrorKind::Expression(format!(
-                "{} must be greater than or equal to {}. ('{}' \
-                 specified.)",
-                Self::name(),
-                Self::inclusive_min(),
-                i
-            ))
-            .into()),
-            i if i > Self::inclusive_max() => Err(ErrorKind::Expression(format!(
-                "{} must be less than {}. ('{}' specified.)",
-                Self::name(),
-                Self::inclusive_max(),
-                i
-            ))
+            i if i < Self::inclusive_min() || i > Self::inclusive_max() => Err(TokenError {
+                field: Some(Self::name()),
+                offset: 0,
+                token: i.to_string(),
+                range: Some((Self::inclusive_min(), Self::inclusive_max())),
+                message: format!(
+                    "{} is outside {}-{}",
+                    i,
+                    Self::inclusive_min(),
+                    Self::inclusive_max()
+                ),
+            }
             .into()),
             i => Ok(i),
         }
@@ -165,8 +219,8 @@ where
             All => Ok(Self::supported_ordinals().clone()),
             Point(ordinal) => Ok(OrdinalSet::from([ordinal])),
             Range(start, end) => {
-                match (Self::validate_ordinal(start), Self::validate_ordinal(end)) {
-                    (Ok(start), Ok(end)) if start <= end => Ok((start..end + 1).collect()),
+                match (Self::validate_ordinal(start)?, Self::validate_ordinal(end)?) {
+                    (start, end) if start <= end => Ok((start..end + 1).collect()),
                     _ => Err(ErrorKind::Expression(format!(
                         "Invalid range for {}: {}-{}",
                         Self::name(),
@@ -179,8 +233,8 @@ where
             NamedRange(ref start_name, ref end_name) => {
                 let start = Self::ordinal_from_name(start_name)?;
                 let end = Self::ordinal_from_name(end_name)?;
-                match (Self::validate_ordinal(start), Self::validate_ordinal(end)) {
-                    (Ok(start), Ok(end)) if start <= end => Ok((start..end + 1).collect()),
+                match (Self::validate_ordinal(start)?, Self::validate_ordinal(end)?) {
+                    (start, end) if start <= end => Ok((start..end + 1).collect()),
                     _ => Err(ErrorKind::Expression(format!(
                         "Invalid named range for {}: {}-{}",
                         Self::name(),
@@ -190,6 +244,13 @@ where
                     .into()),
                 }
             }
+            // Days resolved in each month don't stand for a set of ordinals.
+            _ => Err(ErrorKind::Expression(format!(
+                "Invalid specifier for {}: {:?}",
+                Self::name(),
+                specifier
+            ))
+            .into()),
         }
     }
 
@@ -218,3 +279,16 @@ where
         Ok(ordinals)
     }
 }
+
+/// Hashes the seed of a schedule with the 64-bit FNV-1a function, salted with the name of a field.
+/// The high half is folded into the low half, which alone spreads poorly over small ranges.
+fn seed_hash(seed: &[u8], salt: &str) -> u64 {
+    let hash = salt
+        .as_bytes()
+        .iter()
+        .chain(seed)
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    hash ^ (hash >> 32)
+}
@@ -0,0 +1,521 @@
+//! Timezones with daylight saving time rules, small enough to be evaluated in the Solana runtime.
+//!
+//! A [Tz](struct.Tz.html) is described by a POSIX TZ rule (e.g. `EST5EDT,M3.2.0,M11.1.0`), which gives the
+//! standard and daylight saving offsets of the zone and the yearly transitions between them. IANA names are
+//! resolved with an embedded table of the current rules of common zones: historical rule changes are not
+//! represented, so dates before the current rules came into effect may be off.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use chrono::{
+    DateTime, Datelike, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone,
+};
+
+use crate::error::{Error, ErrorKind};
+
+/// The IANA names of the embedded zones and their POSIX TZ rules, sorted by name.
+const ZONES: &[(&str, &str)] = &[
+    ("Africa/Cairo", "EET-2EEST,M4.5.5/0,M10.5.4/24"),
+    ("Africa/Johannesburg", "SAST-2"),
+    ("Africa/Lagos", "WAT-1"),
+    ("Africa/Nairobi", "EAT-3"),
+    ("America/Anchorage", "AKST9AKDT,M3.2.0,M11.1.0"),
+    ("America/Argentina/Buenos_Aires", "<-03>3"),
+    ("America/Bogota", "<-05>5"),
+    ("America/Chicago", "CST6CDT,M3.2.0,M11.1.0"),
+    ("America/Denver", "MST7MDT,M3.2.0,M11.1.0"),
+    ("America/Halifax", "AST4ADT,M3.2.0,M11.1.0"),
+    ("America/Lima", "<-05>5"),
+    ("America/Los_Angeles", "PST8PDT,M3.2.0,M11.1.0"),
+    ("America/Mexico_City", "CST6"),
+    ("America/New_York", "EST5EDT,M3.2.0,M11.1.0"),
+    ("America/Phoenix", "MST7"),
+    ("America/Santiago", "<-04>4<-03>,M9.1.6/24,M4.1.6/24"),
+    ("America/Sao_Paulo", "<-03>3"),
+    ("America/Toronto", "EST5EDT,M3.2.0,M11.1.0"),
+    ("America/Vancouver", "PST8PDT,M3.2.0,M11.1.0"),
+    ("Asia/Bangkok", "<+07>-7"),
+    ("Asia/Dhaka", "<+06>-6"),
+    ("Asia/Dubai", "<+04>-4"),
+    ("Asia/Ho_Chi_Minh", "<+07>-7"),
+    ("Asia/Hong_Kong", "HKT-8"),
+    ("Asia/Jakarta", "WIB-7"),
+    ("Asia/Jerusalem", "IST-2IDT,M3.4.4/26,M10.5.0"),
+    ("Asia/Karachi", "PKT-5"),
+    ("Asia/Kathmandu", "<+0545>-5:45"),
+    ("Asia/Kolkata", "IST-5:30"),
+    ("Asia/Kuala_Lumpur", "<+08>-8"),
+    ("Asia/Manila", "PST-8"),
+    ("Asia/Riyadh", "<+03>-3"),
+    ("Asia/Seoul", "KST-9"),
+    ("Asia/Shanghai", "CST-8"),
+    ("Asia/Singapore", "<+08>-8"),
+    ("Asia/Taipei", "CST-8"),
+    ("Asia/Tehran", "<+0330>-3:30"),
+    ("Asia/Tokyo", "JST-9"),
+    ("Australia/Adelaide", "ACST-9:30ACDT,M10.1.0,M4.1.0/3"),
+    ("Australia/Brisbane", "AEST-10"),
+    ("Australia/Darwin", "ACST-9:30"),
+    ("Australia/Melbourne", "AEST-10AEDT,M10.1.0,M4.1.0/3"),
+    ("Australia/Perth", "AWST-8"),
+    ("Australia/Sydney", "AEST-10AEDT,M10.1.0,M4.1.0/3"),
+    ("Etc/GMT", "GMT0"),
+    ("Etc/UTC", "UTC0"),
+    ("Europe/Amsterdam", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Athens", "EET-2EEST,M3.5.0/3,M10.5.0/4"),
+    ("Europe/Berlin", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Brussels", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Bucharest", "EET-2EEST,M3.5.0/3,M10.5.0/4"),
+    ("Europe/Budapest", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Copenhagen", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Dublin", "GMT0IST,M3.5.0/1,M10.5.0"),
+    ("Europe/Helsinki", "EET-2EEST,M3.5.0/3,M10.5.0/4"),
+    ("Europe/Istanbul", "<+03>-3"),
+    ("Europe/Kyiv", "EET-2EEST,M3.5.0/3,M10.5.0/4"),
+    ("Europe/Lisbon", "WET0WEST,M3.5.0/1,M10.5.0"),
+    ("Europe/London", "GMT0BST,M3.5.0/1,M10.5.0"),
+    ("Europe/Madrid", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Moscow", "MSK-3"),
+    ("Europe/Oslo", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Paris", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Prague", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Rome", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Stockholm", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Vienna", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Warsaw", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Zurich", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Pacific/Auckland", "NZST-12NZDT,M9.5.0,M4.1.0/3"),
+    ("Pacific/Honolulu", "HST10"),
+    ("UTC", "UTC0"),
+];
+
+/// The default local time of transitions, 02:00:00.
+const DEFAULT_TRANSITION_TIME: i32 = 2 * 3600;
+
+/// The length of the binary form of a timezone.
+pub(crate) const TZ_LEN: usize = 23;
+
+/// A timezone with an optional yearly daylight saving time rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tz {
+    /// The standard offset, in seconds east of UTC.
+    std_offset: i32,
+    dst: Option<Dst>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Dst {
+    /// The daylight saving offset, in seconds east of UTC.
+    offset: i32,
+    /// The transition from standard to daylight saving time, in standard time.
+    start: Transition,
+    /// The transition from daylight saving to standard time, in daylight saving time.
+    end: Transition,
+}
+
+/// A yearly transition on the `week`th `weekday` of `month` (`Mm.w.d`), the 5th week being the last one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Transition {
+    month: u32,
+    week: u32,
+    /// The day of the week, from 0 (Sunday) to 6.
+    weekday: u32,
+    /// The local time of the transition, in seconds after midnight. May be negative or exceed a day.
+    time: i32,
+}
+
+impl Tz {
+    /// Coordinated Universal Time.
+    pub const UTC: Tz = Tz {
+        std_offset: 0,
+        dst: None,
+    };
+
+    /// Resolves an IANA timezone name of the embedded table.
+    pub fn from_name(name: &str) -> Option<Tz> {
+        let index = ZONES.binary_search_by_key(&name, |(name, _)| name).ok()?;
+        Tz::from_posix(ZONES[index].1).ok()
+    }
+
+    /// Parses a POSIX TZ rule, e.g. `CET-1CEST,M3.5.0,M10.5.0/3`.
+    /// Only the `Mm.w.d` form of transition dates is supported.
+    pub fn from_posix(rule: &str) -> Result<Tz, Error> {
+        let invalid = || Error::from(ErrorKind::Expression(format!("Invalid timezone: {}", rule)));
+        let mut parser = PosixParser(rule);
+
+        parser.name().ok_or_else(invalid)?;
+        let std_offset = -parser.time().ok_or_else(invalid)?;
+        if FixedOffset::east_opt(std_offset).is_none() {
+            return Err(invalid());
+        }
+        if parser.0.is_empty() {
+            return Ok(Tz {
+                std_offset,
+                dst: None,
+            });
+        }
+
+        parser.name().ok_or_else(invalid)?;
+        let offset = if parser.0.starts_with(',') {
+            std_offset + 3600
+        } else {
+            -parser.time().ok_or_else(invalid)?
+        };
+        let start = parser.transition().ok_or_else(invalid)?;
+        let end = parser.transition().ok_or_else(invalid)?;
+        if !parser.0.is_empty() || FixedOffset::east_opt(offset).is_none() {
+            return Err(invalid());
+        }
+
+        Ok(Tz {
+            std_offset,
+            dst: Some(Dst { offset, start, end }),
+        })
+    }
+
+    /// Returns the offset in effect at a unix timestamp, in seconds east of UTC.
+    fn offset_at(&self, timestamp: i64) -> i32 {
+        let Some(dst) = self.dst else {
+            return self.std_offset;
+        };
+        let Some(year) = DateTime::from_timestamp(timestamp + self.std_offset as i64, 0)
+            .map(|local| local.year())
+        else {
+            return self.std_offset;
+        };
+        let (Some(start), Some(end)) = (
+            dst.start.timestamp(year, self.std_offset),
+            dst.end.timestamp(year, dst.offset),
+        ) else {
+            return self.std_offset;
+        };
+
+        // In the southern hemisphere, daylight saving time spans the new year.
+        let is_dst = if start < end {
+            start <= timestamp && timestamp < end
+        } else {
+            !(end <= timestamp && timestamp < start)
+        };
+        if is_dst {
+            dst.offset
+        } else {
+            self.std_offset
+        }
+    }
+
+    /// Encodes the timezone into its binary form, see `CompiledSchedule`.
+    pub(crate) fn to_bytes(self) -> [u8; TZ_LEN] {
+        let mut bytes = [0; TZ_LEN];
+        bytes[1..5].copy_from_slice(&self.std_offset.to_le_bytes());
+        if let Some(dst) = self.dst {
+            bytes[0] = 1;
+            bytes[5..9].copy_from_slice(&dst.offset.to_le_bytes());
+            bytes[9..16].copy_from_slice(&dst.start.to_bytes());
+            bytes[16..23].copy_from_slice(&dst.end.to_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes a timezone from its binary form, if it's valid.
+    pub(crate) fn from_bytes(bytes: &[u8; TZ_LEN]) -> Option<Tz> {
+        let offset = |bytes: &[u8]| {
+            let offset = i32::from_le_bytes(bytes.try_into().ok()?);
+            FixedOffset::east_opt(offset).map(|_| offset)
+        };
+        let std_offset = offset(&bytes[1..5])?;
+        let dst = match bytes[0] {
+            0 if bytes[5..].iter().all(|byte| *byte == 0) => None,
+            1 => Some(Dst {
+                offset: offset(&bytes[5..9])?,
+                start: Transition::from_bytes(&bytes[9..16])?,
+                end: Transition::from_bytes(&bytes[16..23])?,
+            }),
+            _ => return None,
+        };
+        Some(Tz { std_offset, dst })
+    }
+
+    fn tz_offset(&self, offset: i32) -> TzOffset {
+        TzOffset {
+            tz: *self,
+            offset: FixedOffset::east_opt(offset).unwrap(),
+        }
+    }
+}
+
+impl FromStr for Tz {
+    type Err = Error;
+
+    /// Parses an IANA timezone name of the embedded table, or a POSIX TZ rule.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Tz::from_name(s) {
+            Some(tz) => Ok(tz),
+            None => Tz::from_posix(s),
+        }
+    }
+}
+
+impl Transition {
+    fn to_bytes(self) -> [u8; 7] {
+        let mut bytes = [
+            self.month as u8,
+            self.week as u8,
+            self.weekday as u8,
+            0,
+            0,
+            0,
+            0,
+        ];
+        bytes[3..].copy_from_slice(&self.time.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Transition> {
+        let (month, week, weekday) = (bytes[0] as u32, bytes[1] as u32, bytes[2] as u32);
+        if !(1..=12).contains(&month) || !(1..=5).contains(&week) || weekday > 6 {
+            return None;
+        }
+        Some(Transition {
+            month,
+            week,
+            weekday,
+            time: i32::from_le_bytes(bytes[3..7].try_into().ok()?),
+        })
+    }
+
+    /// Returns the unix timestamp of the transition in a year, given the offset in effect before it.
+    fn timestamp(&self, year: i32, offset_before: i32) -> Option<i64> {
+        let first = NaiveDate::from_ymd_opt(year, self.month, 1)?;
+        let first_weekday = first.weekday().num_days_from_sunday();
+        let mut day = 1 + (self.weekday + 7 - first_weekday) % 7 + (self.week - 1) * 7;
+        while NaiveDate::from_ymd_opt(year, self.month, day).is_none() {
+            day -= 7;
+        }
+        let midnight = NaiveDate::from_ymd_opt(year, self.month, day)?
+            .and_hms_opt(0, 0, 0)?
+            .and_utc()
+            .timestamp();
+        Some(midnight + self.time as i64 - offset_before as i64)
+    }
+}
+
+/// A minimal parser of POSIX TZ rules.
+struct PosixParser<'a>(&'a str);
+
+impl PosixParser<'_> {
+    /// Parses a zone abbreviation, either alphabetic or quoted in angle brackets.
+    fn name(&mut self) -> Option<()> {
+        let len = if let Some(quoted) = self.0.strip_prefix('<') {
+            quoted.find('>')? + 2
+        } else {
+            self.0
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(self.0.len())
+        };
+        if len < 3 {
+            return None;
+        }
+        self.0 = &self.0[len..];
+        Some(())
+    }
+
+    /// Parses a signed `hh[:mm[:ss]]` duration, in seconds.
+    fn time(&mut self) -> Option<i32> {
+        let sign = match self.0.chars().next()? {
+            '-' => -1,
+            '+' => 1,
+            _ => 0,
+        };
+        if sign != 0 {
+            self.0 = &self.0[1..];
+        }
+        let mut seconds = 0;
+        for (i, unit) in [3600, 60, 1].into_iter().enumerate() {
+            if i > 0 {
+                match self.0.strip_prefix(':') {
+                    Some(rest) => self.0 = rest,
+                    None => break,
+                }
+            }
+            seconds += self.number()? * unit;
+        }
+        Some(if sign < 0 { -seconds } else { seconds })
+    }
+
+    /// Parses a `,Mm.w.d[/time]` transition.
+    fn transition(&mut self) -> Option<Transition> {
+        self.0 = self.0.strip_prefix(",M")?;
+        let month = self.number()? as u32;
+        self.0 = self.0.strip_prefix('.')?;
+        let week = self.number()? as u32;
+        self.0 = self.0.strip_prefix('.')?;
+        let weekday = self.number()? as u32;
+        let time = match self.0.strip_prefix('/') {
+            Some(rest) => {
+                self.0 = rest;
+                self.time()?
+            }
+            None => DEFAULT_TRANSITION_TIME,
+        };
+        if !(1..=12).contains(&month) || !(1..=5).contains(&week) || weekday > 6 {
+            return None;
+        }
+        Some(Transition {
+            month,
+            week,
+            weekday,
+            time,
+        })
+    }
+
+    fn number(&mut self) -> Option<i32> {
+        let len = self
+            .0
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.0.len());
+        if len == 0 || len > 3 {
+            return None;
+        }
+        let number = self.0[..len].parse().ok()?;
+        self.0 = &self.0[len..];
+        Some(number)
+    }
+}
+
+/// The offset of a [Tz](struct.Tz.html) at a given moment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TzOffset {
+    tz: Tz,
+    offset: FixedOffset,
+}
+
+impl Offset for TzOffset {
+    fn fix(&self) -> FixedOffset {
+        self.offset
+    }
+}
+
+impl Display for TzOffset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.offset)
+    }
+}
+
+impl TimeZone for Tz {
+    type Offset = TzOffset;
+
+    fn from_offset(offset: &TzOffset) -> Self {
+        offset.tz
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<TzOffset> {
+        self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<TzOffset> {
+        let timestamp = local.and_utc().timestamp();
+        let offsets = match self.dst {
+            None => return LocalResult::Single(self.tz_offset(self.std_offset)),
+            // The earliest moment has the largest offset.
+            Some(dst) => [
+                self.std_offset.max(dst.offset),
+                self.std_offset.min(dst.offset),
+            ],
+        };
+        let mut valid_offsets = offsets
+            .into_iter()
+            .filter(|offset| self.offset_at(timestamp - *offset as i64) == *offset);
+        match (valid_offsets.next(), valid_offsets.next()) {
+            (None, _) => LocalResult::None,
+            (Some(offset), None) => LocalResult::Single(self.tz_offset(offset)),
+            (Some(earliest), Some(latest)) if earliest == latest => {
+                LocalResult::Single(self.tz_offset(earliest))
+            }
+            (Some(earliest), Some(latest)) => {
+                LocalResult::Ambiguous(self.tz_offset(earliest), self.tz_offset(latest))
+            }
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> TzOffset {
+        self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> TzOffset {
+        self.tz_offset(self.offset_at(utc.and_utc().timestamp()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_embedded_zones() {
+        assert!(ZONES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        for (name, _) in ZONES {
+            assert!(Tz::from_name(name).is_some(), "{}", name);
+        }
+        assert_eq!(Tz::from_name("Mars/Olympus_Mons"), None);
+    }
+
+    #[test]
+    fn test_posix_rules() {
+        assert_eq!(Tz::from_str("UTC0").unwrap(), Tz::UTC);
+        assert_eq!(
+            Tz::from_str("<+0545>-5:45").unwrap().std_offset,
+            5 * 3600 + 45 * 60
+        );
+        assert!(Tz::from_str("EST5EDT").is_err());
+        assert!(Tz::from_str("EST5EDT,M3.2.0,M13.1.0").is_err());
+        assert!(Tz::from_str("E5").is_err());
+    }
+
+    #[test]
+    fn test_offsets() {
+        let new_york = Tz::from_name("America/New_York").unwrap();
+        let utc = |y, m, d, h, min| Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap();
+
+        // DST starts on 2024-03-10 at 02:00 EST and ends on 2024-11-03 at 02:00 EDT.
+        let offset = |datetime: chrono::DateTime<Utc>| {
+            datetime
+                .with_timezone(&new_york)
+                .offset()
+                .fix()
+                .local_minus_utc()
+                / 3600
+        };
+        assert_eq!(offset(utc(2024, 3, 10, 6, 59)), -5);
+        assert_eq!(offset(utc(2024, 3, 10, 7, 0)), -4);
+        assert_eq!(offset(utc(2024, 11, 3, 5, 59)), -4);
+        assert_eq!(offset(utc(2024, 11, 3, 6, 0)), -5);
+
+        // Local times in the gap don't exist, those in the overlap are ambiguous.
+        assert_eq!(
+            new_york.with_ymd_and_hms(2024, 3, 10, 2, 30, 0),
+            LocalResult::None
+        );
+        match new_york.with_ymd_and_hms(2024, 11, 3, 1, 30, 0) {
+            LocalResult::Ambiguous(earliest, latest) => {
+                assert_eq!(earliest, utc(2024, 11, 3, 5, 30));
+                assert_eq!(latest, utc(2024, 11, 3, 6, 30));
+            }
+            result => panic!("unexpected {:?}", result),
+        }
+
+        // Southern hemisphere: DST from 2024-10-06 to 2025-04-06 in Sydney.
+        let sydney = Tz::from_name("Australia/Sydney").unwrap();
+        let offset = |datetime: chrono::DateTime<Utc>| {
+            datetime
+                .with_timezone(&sydney)
+                .offset()
+                .fix()
+                .local_minus_utc()
+                / 3600
+        };
+        assert_eq!(offset(utc(2024, 7, 1, 0, 0)), 10);
+        assert_eq!(offset(utc(2025, 1, 1, 0, 0)), 11);
+    }
+}
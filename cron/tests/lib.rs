@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use chrono::*;
-    use sablier_cron::{Schedule, TimeUnitSpec};
+    use sablier_cron::{Calendar, CompiledSchedule, Dates, Dialect, Schedule, TimeUnitSpec, Tz};
     use std::str::FromStr;
 
     #[test]
@@ -246,4 +246,660 @@ mod tests {
         assert!(schedule.minutes().is_all());
         assert!(schedule.seconds().is_all());
     }
+
+    #[test]
+    fn test_timezone() {
+        let schedule = Schedule::from_str("CRON_TZ=Europe/Paris 0 30 9 * * Mon-Fri").unwrap();
+        assert!(schedule.timezone().is_some());
+        assert_eq!(
+            schedule.to_string(),
+            "CRON_TZ=Europe/Paris 0 30 9 * * Mon-Fri"
+        );
+
+        // 09:30 in Paris is 07:30 UTC in summer and 08:30 UTC in winter.
+        let start_time = Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            schedule.next_after(&start_time).unwrap(),
+            Utc.with_ymd_and_hms(2024, 7, 1, 7, 30, 0).unwrap()
+        );
+        let start_time = Utc.with_ymd_and_hms(2024, 12, 2, 0, 0, 0).unwrap();
+        assert_eq!(
+            schedule.next_after(&start_time).unwrap(),
+            Utc.with_ymd_and_hms(2024, 12, 2, 8, 30, 0).unwrap()
+        );
+        assert!(schedule.includes(Utc.with_ymd_and_hms(2024, 12, 2, 8, 30, 0).unwrap()));
+
+        assert!(Schedule::from_str("TZ=Mars/Olympus_Mons 0 30 9 * * *").is_err());
+        assert!(Schedule::from_str("TZ=EST5EDT,M3.2.0,M11.1.0 0 30 9 * * *").is_ok());
+    }
+
+    #[test]
+    fn test_timezone_dst_gap() {
+        // On 2024-03-10, New York clocks skip from 02:00 EST to 03:00 EDT.
+        let schedule = Schedule::from_str("TZ=America/New_York 0 30 2 * * *").unwrap();
+        let start_time = Utc.with_ymd_and_hms(2024, 3, 9, 12, 0, 0).unwrap();
+        let upcoming: Vec<_> = schedule.after(&start_time).take(2).collect();
+        assert_eq!(
+            upcoming,
+            vec![
+                // The skipped 02:30 fires at 03:30 EDT.
+                Utc.with_ymd_and_hms(2024, 3, 10, 7, 30, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 3, 11, 6, 30, 0).unwrap(),
+            ]
+        );
+        assert_eq!(schedule.prev_before(&upcoming[1]).unwrap(), upcoming[0]);
+    }
+
+    #[test]
+    fn test_timezone_dst_overlap() {
+        // On 2024-11-03, New York clocks go back from 02:00 EDT to 01:00 EST.
+        let schedule = Schedule::from_str("TZ=America/New_York 0 30 1 * * *").unwrap();
+        let start_time = Utc.with_ymd_and_hms(2024, 11, 2, 12, 0, 0).unwrap();
+        let upcoming: Vec<_> = schedule.after(&start_time).take(2).collect();
+        assert_eq!(
+            upcoming,
+            vec![
+                // The repeated 01:30 fires once, at 01:30 EDT.
+                Utc.with_ymd_and_hms(2024, 11, 3, 5, 30, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 11, 4, 6, 30, 0).unwrap(),
+            ]
+        );
+
+        // From the second occurrence of 01:10, the next 01:30 is its second occurrence.
+        let start_time = Utc.with_ymd_and_hms(2024, 11, 3, 6, 10, 0).unwrap();
+        assert_eq!(
+            schedule.next_after(&start_time).unwrap(),
+            Utc.with_ymd_and_hms(2024, 11, 3, 6, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_last_day_of_month() {
+        let schedule = Schedule::from_str("0 0 12 L * ?").unwrap();
+        let start_time = Utc.with_ymd_and_hms(2024, 1, 31, 13, 0, 0).unwrap();
+        let upcoming: Vec<_> = schedule.after(&start_time).take(3).collect();
+        assert_eq!(
+            upcoming,
+            vec![
+                Utc.with_ymd_and_hms(2024, 2, 29, 12, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 3, 31, 12, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 4, 30, 12, 0, 0).unwrap(),
+            ]
+        );
+        assert_eq!(
+            schedule.prev_before(&upcoming[0]).unwrap(),
+            start_time - Duration::hours(1)
+        );
+        assert!(schedule.includes(upcoming[1]));
+        assert!(!schedule.includes(Utc.with_ymd_and_hms(2024, 4, 29, 12, 0, 0).unwrap()));
+
+        let schedule = Schedule::from_str("0 0 12 1,L-2 * ?").unwrap();
+        let start_time = Utc.with_ymd_and_hms(2024, 2, 1, 13, 0, 0).unwrap();
+        let upcoming: Vec<_> = schedule.after(&start_time).take(2).collect();
+        assert_eq!(
+            upcoming,
+            vec![
+                Utc.with_ymd_and_hms(2024, 2, 27, 12, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weekdays_of_month() {
+        // The last business day of the month: 2024-06-30 is a Sunday and 2024-08-31 a Saturday.
+        let schedule = Schedule::from_str("0 0 16 LW * ?").unwrap();
+        let start_time = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let upcoming: Vec<_> = schedule.after(&start_time).take(3).collect();
+        assert_eq!(
+            upcoming,
+            vec![
+                Utc.with_ymd_and_hms(2024, 6, 28, 16, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 7, 31, 16, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 8, 30, 16, 0, 0).unwrap(),
+            ]
+        );
+
+        // The weekday nearest to the 1st doesn't leave the month: 2024-06-01 is a Saturday.
+        let schedule = Schedule::from_str("0 0 0 1W * ?").unwrap();
+        let start_time = Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap();
+        assert_eq!(
+            schedule.next_after(&start_time).unwrap(),
+            Utc.with_ymd_and_hms(2024, 6, 3, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_days_of_week_of_month() {
+        // The third Friday of the month.
+        let schedule = Schedule::from_str("0 0 8 ? * FRI#3").unwrap();
+        let start_time = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let upcoming: Vec<_> = schedule.after(&start_time).take(2).collect();
+        assert_eq!(
+            upcoming,
+            vec![
+                Utc.with_ymd_and_hms(2024, 6, 21, 8, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 7, 19, 8, 0, 0).unwrap(),
+            ]
+        );
+        assert_eq!(
+            schedule.prev_before(&upcoming[0]).unwrap(),
+            Utc.with_ymd_and_hms(2024, 5, 17, 8, 0, 0).unwrap()
+        );
+        assert_eq!(
+            Schedule::from_str("0 0 8 ? * 6#3")
+                .unwrap()
+                .next_after(&start_time),
+            Some(upcoming[0])
+        );
+
+        // The last Friday of the month, and Mondays.
+        let schedule = Schedule::from_str("0 0 8 ? * MON,FRIL").unwrap();
+        let upcoming: Vec<_> = schedule.after(&start_time).take(5).collect();
+        assert_eq!(
+            upcoming
+                .iter()
+                .map(|datetime| datetime.day())
+                .collect::<Vec<_>>(),
+            vec![3, 10, 17, 24, 28]
+        );
+
+        // Days of week are combined with days of month.
+        let schedule = Schedule::from_str("0 0 8 1-7 * 6L").unwrap();
+        assert_eq!(schedule.after(&start_time).next(), None);
+    }
+
+    #[test]
+    fn test_invalid_day_specifiers() {
+        for expression in [
+            "0 0 0 L-31 * ?",
+            "0 0 0 32W * ?",
+            "0 0 0 FRI#3 * ?",
+            "0 0 0 ? * L",
+            "0 0 0 ? * FRI#6",
+            "0 0 0 ? * 8L",
+            "0 0 0 ? * BEARL",
+            "0 0 L * * ?",
+            "0 0 0 L/2 * ?",
+        ] {
+            assert!(Schedule::from_str(expression).is_err(), "{}", expression);
+        }
+        assert!(Schedule::from_str("0 0 0 * April,July ?").is_ok());
+    }
+
+    #[test]
+    fn test_dialects() {
+        let schedule = Schedule::from_str("*/5 * * * *").unwrap();
+        assert_eq!(schedule.dialect(), Some(Dialect::Unix));
+        let start_time = Utc.with_ymd_and_hms(2024, 6, 1, 0, 2, 30).unwrap();
+        let upcoming: Vec<_> = schedule.after(&start_time).take(2).collect();
+        assert_eq!(
+            upcoming,
+            vec![
+                Utc.with_ymd_and_hms(2024, 6, 1, 0, 5, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 6, 1, 0, 10, 0).unwrap(),
+            ]
+        );
+
+        assert_eq!(
+            Schedule::from_str("0 */5 * * * *").unwrap().dialect(),
+            Some(Dialect::Seconds)
+        );
+        assert_eq!(
+            Schedule::from_str("0 */5 * * * * 2024").unwrap().dialect(),
+            Some(Dialect::Years)
+        );
+        assert_eq!(Schedule::from_str("@daily").unwrap().dialect(), None);
+
+        assert!(Schedule::parse_as("*/5 * * * *", Dialect::Unix).is_ok());
+        assert!(Schedule::parse_as("*/5 * * * *", Dialect::Seconds).is_err());
+        assert!(Schedule::parse_as("0 */5 * * * *", Dialect::Unix).is_err());
+        assert!(Schedule::parse_as("@hourly", Dialect::Years).is_ok());
+    }
+
+    #[test]
+    fn test_unix_days_of_week() {
+        // Unix crontabs number days of the week from 0 (Sunday): 1-5 is Monday to Friday.
+        let schedule = Schedule::from_str("0 9 * * 1-5").unwrap();
+        assert!(schedule.timeunitspec_eq(&Schedule::from_str("0 0 9 * * Mon-Fri").unwrap()));
+        let schedule = Schedule::from_str("0 9 * * 5-7").unwrap();
+        assert!(schedule.timeunitspec_eq(&Schedule::from_str("0 0 9 * * Fri,Sat,Sun").unwrap()));
+        let schedule = Schedule::from_str("0 9 * * 0").unwrap();
+        assert!(schedule.timeunitspec_eq(&Schedule::from_str("0 0 9 * * Sun").unwrap()));
+        let schedule = Schedule::from_str("0 9 ? * 5#3").unwrap();
+        assert!(schedule.timeunitspec_eq(&Schedule::from_str("0 0 9 ? * FRI#3").unwrap()));
+        assert!(Schedule::from_str("0 9 * * 8").is_err());
+    }
+
+    #[test]
+    fn test_unix_days_union() {
+        // Unix crontabs match either day field when both are restricted.
+        // 2024-06-01 is a Saturday.
+        let schedule = Schedule::from_str("0 0 13 * 5").unwrap();
+        let start_time = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let upcoming: Vec<_> = schedule.after(&start_time).take(4).collect();
+        assert_eq!(
+            upcoming
+                .iter()
+                .map(|datetime| datetime.day())
+                .collect::<Vec<_>>(),
+            vec![7, 13, 14, 21]
+        );
+
+        // Fields starting with '*' still restrict the other one.
+        let schedule = Schedule::from_str("0 0 */2 * 5").unwrap();
+        let upcoming: Vec<_> = schedule.after(&start_time).take(2).collect();
+        assert_eq!(
+            upcoming
+                .iter()
+                .map(|datetime| datetime.day())
+                .collect::<Vec<_>>(),
+            vec![7, 21]
+        );
+    }
+
+    #[test]
+    fn test_shorthands() {
+        assert!(Schedule::from_str("@annually")
+            .unwrap()
+            .timeunitspec_eq(&Schedule::from_str("@yearly").unwrap()));
+        assert!(Schedule::from_str("@midnight")
+            .unwrap()
+            .timeunitspec_eq(&Schedule::from_str("@daily").unwrap()));
+        assert!(Schedule::from_str("@reboot").is_err());
+
+        for (every, expression) in [
+            ("@every 15s", "*/15 * * * * *"),
+            ("@every 5m", "0 */5 * * * *"),
+            ("@every 120s", "0 */2 * * * *"),
+            ("@every 6h", "0 0 */6 * * *"),
+            ("@every 1d", "0 0 0 * * *"),
+        ] {
+            let expected = Schedule::from_str(expression).unwrap();
+            assert!(
+                Schedule::from_str(every)
+                    .unwrap()
+                    .timeunitspec_eq(&expected),
+                "{}",
+                every
+            );
+        }
+        for every in [
+            "@every",
+            "@every 0s",
+            "@every 7m",
+            "@every 5h",
+            "@every 2d",
+            "@every 5x",
+        ] {
+            assert!(Schedule::from_str(every).is_err(), "{}", every);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let token = |expression: &str| {
+            let error = Schedule::from_str(expression).unwrap_err();
+            let token = error.token().expect(expression).clone();
+            (
+                token.field.map(|field| field.into_owned()),
+                token.offset,
+                token.token,
+                token.range,
+                error.to_string(),
+            )
+        };
+
+        assert_eq!(
+            token("0 0 25 * * *"),
+            (
+                Some("Hours".to_owned()),
+                4,
+                "25".to_owned(),
+                Some((0, 23)),
+                "Invalid expression: hours: 25 is outside 0-23 (at byte 4, '25')".to_owned()
+            )
+        );
+        assert_eq!(token("0 0 1,2,20-25 * * *").2, "20-25");
+        assert_eq!(token("0 0 1,2,20-25 * * *").1, 8);
+        assert_eq!(token("*/5 * * * 8").3, Some((0, 7)));
+        assert_eq!(token("*/5 * * * 8").0, Some("Days of Week".to_owned()));
+        assert_eq!(
+            token("CRON_TZ=Europe/Paris 0 61 * * * *").0,
+            Some("Minutes".to_owned())
+        );
+        assert_eq!(token("CRON_TZ=Europe/Paris 0 61 * * * *").1, 23);
+        assert_eq!(token("CRON_TZ=Mars/Olympus_Mons 0 * * * * *").1, 8);
+        assert_eq!(token("0 0 0 * Foo *").2, "Foo");
+        assert_eq!(token("0 0 ? * * *").0, Some("Hours".to_owned()));
+        assert_eq!(token("0 0 0 1 * * 2024 *").2, "*");
+        assert_eq!(token("@dailyBla").2, "@dailyBla");
+        assert_eq!(token("@every 7m").2, "7m");
+        assert_eq!(token("@daily *").2, "*");
+        assert!(Schedule::from_str("* * *").unwrap_err().token().is_none());
+    }
+
+    #[test]
+    fn test_before() {
+        let schedule = Schedule::from_str("0 0 9 * * Mon-Fri").unwrap();
+        // 2024-06-10 is a Monday.
+        let end = Utc.with_ymd_and_hms(2024, 6, 10, 9, 0, 0).unwrap();
+        let previous: Vec<_> = schedule.before(&end).take(3).collect();
+        assert_eq!(
+            previous,
+            vec![
+                Utc.with_ymd_and_hms(2024, 6, 7, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 6, 6, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 6, 5, 9, 0, 0).unwrap(),
+            ]
+        );
+        assert_eq!(schedule.prev_before(&end), Some(previous[0]));
+        assert!(schedule.includes(end));
+        assert!(schedule.includes(end + Duration::milliseconds(500)));
+        assert!(!schedule.includes(end + Duration::seconds(1)));
+    }
+
+    #[test]
+    fn test_between() {
+        let schedule = Schedule::from_str("0 0 * * * *").unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap();
+
+        // The start is included and the end excluded.
+        let moments: Vec<_> = schedule.between(&start, &end).collect();
+        assert_eq!(moments.len(), 30 * 24);
+        assert_eq!(moments.first(), Some(&start));
+        assert_eq!(
+            moments.last(),
+            Some(&Utc.with_ymd_and_hms(2024, 6, 30, 23, 0, 0).unwrap())
+        );
+        assert_eq!(schedule.count_between(&start, &end), 30 * 24);
+
+        // Fractions of a second are respected.
+        let start = start + Duration::milliseconds(1);
+        assert_eq!(schedule.count_between(&start, &end), 30 * 24 - 1);
+        assert_eq!(schedule.count_between(&end, &start), 0);
+
+        // Bounded queries work with schedules that end.
+        let schedule = Schedule::from_str("0 0 0 1 1 * 2020-2022").unwrap();
+        let start = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2100, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(schedule.count_between(&start, &end), 3);
+    }
+
+    #[test]
+    fn test_describe() {
+        let descriptions = [
+            (
+                "0 30 9 * May-Aug Mon,Wed,Fri",
+                "At 09:30 on Monday, Wednesday and Friday in May through August",
+            ),
+            ("0 */5 * * * *", "Every 5 minutes"),
+            ("*/10 * * * * *", "Every 10 seconds"),
+            ("15 * * * * *", "At second 15, every minute"),
+            ("0 15,45 * * * *", "At minutes 15 and 45"),
+            ("@hourly", "Every hour"),
+            (
+                "0 0 9-17 * * Mon-Fri",
+                "Every hour between 09:00 and 17:59 on Monday through Friday",
+            ),
+            ("0 0 0 L * *", "At 00:00 on the last day of the month"),
+            (
+                "0 0 12 15W * *",
+                "At 12:00 on the weekday nearest to day 15 of the month",
+            ),
+            (
+                "0 0 8 * * FRI#3",
+                "At 08:00 on the third Friday of the month",
+            ),
+            (
+                "0 0 0 1 * Mon",
+                "At 00:00 on day 1 of the month, if it is also on Monday",
+            ),
+            (
+                "0 0 0 1 1 * 2030-2032",
+                "At 00:00 on day 1 of the month in January in 2030 through 2032",
+            ),
+            (
+                "CRON_TZ=Europe/Paris 0 0 8 1,15 * *",
+                "At 08:00 on days 1 and 15 of the month (Europe/Paris)",
+            ),
+        ];
+        for (expression, description) in descriptions {
+            let schedule = Schedule::from_str(expression).unwrap();
+            assert_eq!(schedule.describe(), description, "{}", expression);
+            assert_eq!(format!("{:#}", schedule), description);
+            assert_eq!(format!("{}", schedule), expression);
+        }
+
+        let schedule = Schedule::parse_as("0 0 1 * Mon", Dialect::Unix).unwrap();
+        assert_eq!(
+            schedule.describe(),
+            "At 00:00 on day 1 of the month or on Monday"
+        );
+    }
+
+    #[test]
+    fn test_compiled_schedules() {
+        let expressions = [
+            "* * * * * *",
+            "0 30 9 * May-Aug Mon,Wed,Fri",
+            "*/7 */13 */5 * * *",
+            "0 0 0 29 Feb * 2024-2032",
+            "0 0 12 L,L-3,LW,15W * *",
+            "0 0 8 * * FRI#3,6L,2#5",
+            "@every 10m",
+            "CRON_TZ=America/New_York 0 30 1,2 * * *",
+            "TZ=AEST-10AEDT,M10.1.0,M4.1.0/3 0 30 2 * * *",
+        ];
+        let starts = [
+            Utc.with_ymd_and_hms(2024, 1, 31, 23, 59, 59).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 10, 6, 30, 0).unwrap(),
+            Utc.with_ymd_and_hms(2024, 11, 3, 5, 29, 59).unwrap(),
+        ];
+        for expression in expressions {
+            let schedule = Schedule::from_str(expression).unwrap();
+            let compiled = schedule.compile();
+            assert_eq!(compiled.timezone(), schedule.timezone());
+            for start in starts {
+                let expected: Vec<_> = schedule.after(&start).take(40).collect();
+                let actual: Vec<_> = compiled.after(&start).take(40).collect();
+                assert_eq!(actual, expected, "{} after {}", expression, start);
+            }
+        }
+
+        let schedule = Schedule::parse_as("0 0 1 * Mon", Dialect::Unix).unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let expected: Vec<_> = schedule.after(&start).take(20).collect();
+        let actual: Vec<_> = schedule.compile().after(&start).take(20).collect();
+        assert_eq!(actual, expected);
+
+        let schedule = Schedule::from_str("0 0 0 1 1 * 2100").unwrap();
+        let start = Utc.with_ymd_and_hms(2100, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(schedule.compile().next_after(&start), None);
+    }
+
+    #[test]
+    fn test_compiled_bytes() {
+        for expression in [
+            "0 0 0 1 1 * 2024,2100",
+            "0 0 12 LW,L-30,31W * *",
+            "0 0 8 ? * SAT#5",
+            "CRON_TZ=Europe/Paris 0 0 8 * * *",
+        ] {
+            let compiled = Schedule::from_str(expression).unwrap().compile();
+            let bytes = compiled.to_bytes();
+            assert_eq!(bytes.len(), CompiledSchedule::LEN);
+            assert_eq!(CompiledSchedule::from_bytes(&bytes).unwrap(), compiled);
+        }
+
+        let bytes = Schedule::from_str("0 0 8 * * *")
+            .unwrap()
+            .compile()
+            .to_bytes();
+        assert!(CompiledSchedule::from_bytes(&bytes[1..]).is_err());
+        let mut invalid = bytes;
+        invalid[0] = 0;
+        assert!(CompiledSchedule::from_bytes(&invalid).is_err());
+        // Second 60.
+        let mut invalid = bytes;
+        invalid[9] = 0x10;
+        assert!(CompiledSchedule::from_bytes(&invalid).is_err());
+        // Month 13.
+        let mut invalid = bytes;
+        invalid[26] = 0x20;
+        assert!(CompiledSchedule::from_bytes(&invalid).is_err());
+        // A timezone without the timezone flag.
+        let mut invalid = bytes;
+        invalid[CompiledSchedule::LEN - 1] = 1;
+        assert!(CompiledSchedule::from_bytes(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_hashed() {
+        let seeds: Vec<[u8; 32]> = (0..32).map(|i| [i; 32]).collect();
+        let minutes_of = |expression: &str, seed: &[u8]| {
+            Schedule::parse_seeded(expression, seed)
+                .unwrap()
+                .minutes()
+                .count()
+        };
+
+        // The same seed always hashes to the same schedule.
+        let schedule = Schedule::parse_seeded("H H * * * *", &seeds[1]).unwrap();
+        assert!(
+            schedule.timeunitspec_eq(&Schedule::parse_seeded("H H * * * *", &seeds[1]).unwrap())
+        );
+        assert_eq!(schedule.to_string(), "H H * * * *");
+        assert_eq!(schedule.seconds().count(), 1);
+        assert_eq!(schedule.minutes().count(), 1);
+
+        // Different seeds spread over the field.
+        let seconds: std::collections::BTreeSet<u32> = seeds
+            .iter()
+            .map(|seed| {
+                let schedule = Schedule::parse_seeded("H * * * * *", seed).unwrap();
+                (0..60)
+                    .find(|second| schedule.seconds().includes(*second))
+                    .unwrap()
+            })
+            .collect();
+        assert!(seconds.len() > 16);
+
+        for seed in &seeds {
+            let schedule = Schedule::parse_seeded("H(0-29) H/15 H * * *", seed).unwrap();
+            let second = (0..60)
+                .find(|second| schedule.seconds().includes(*second))
+                .unwrap();
+            assert!(second < 30);
+            let minutes: Vec<u32> = (0..60)
+                .filter(|minute| schedule.minutes().includes(*minute))
+                .collect();
+            assert_eq!(minutes.len(), 4);
+            assert!(minutes[0] < 15 && minutes.windows(2).all(|pair| pair[1] - pair[0] == 15));
+
+            let schedule = Schedule::parse_seeded("0 0 0 H * *", seed).unwrap();
+            let day = (1..=31)
+                .find(|day| schedule.days_of_month().includes(*day))
+                .unwrap();
+            assert!(day <= 28);
+
+            let schedule = Schedule::parse_seeded("0 H(10-50)/20 * * * *", seed).unwrap();
+            let minutes: Vec<u32> = (0..60)
+                .filter(|minute| schedule.minutes().includes(*minute))
+                .collect();
+            assert!(minutes[0] >= 10 && minutes[0] < 30 && *minutes.last().unwrap() <= 50);
+            assert!(minutes.windows(2).all(|pair| pair[1] - pair[0] == 20));
+            assert_eq!(minutes_of("H * * * *", seed), 1);
+        }
+
+        // Seconds and minutes hash to different values from the same seed.
+        assert!(seeds.iter().any(|seed| {
+            let schedule = Schedule::parse_seeded("H H * * * *", seed).unwrap();
+            (0..60).any(|n| schedule.seconds().includes(n) != schedule.minutes().includes(n))
+        }));
+
+        // Expressions without a seed hash an empty one.
+        assert!(Schedule::from_str("H * * * * *").is_ok());
+        assert!(Schedule::parse_as_seeded("H * * * *", Dialect::Unix, &seeds[0]).is_ok());
+        assert!(Schedule::parse_as_seeded("H * * * *", Dialect::Seconds, &seeds[0]).is_err());
+
+        for expression in [
+            "H(50-70) * * * * *",
+            "H(30-10) * * * * *",
+            "H/0 * * * * *",
+            "0 0 0 * * H#2",
+            "h * * * * *",
+        ] {
+            assert!(Schedule::from_str(expression).is_err(), "{}", expression);
+        }
+    }
+
+    #[test]
+    fn test_calendar() {
+        let schedule = |expression: &str| Schedule::from_str(expression).unwrap();
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 12, 20, 0, 0, 0).unwrap();
+        let moments = |calendar: &Calendar| calendar.after(&start).take(20).collect::<Vec<_>>();
+
+        // Weekdays at 14:00, except holidays.
+        let holidays = Dates::new([date(2025, 12, 25), date(2026, 1, 1)]);
+        let weekdays = Calendar::from(schedule("0 0 14 * * Mon-Fri"));
+        let calendar = weekdays.clone() - holidays.clone();
+        let days: Vec<u32> = calendar.after(&start).take(6).map(|m| m.day()).collect();
+        assert_eq!(days, [22, 23, 24, 26, 29, 30]);
+        assert_eq!(calendar.after(&start).nth(6).unwrap().day(), 31);
+        assert_eq!(calendar.after(&start).nth(7).unwrap().day(), 2);
+        assert!(calendar.includes(Utc.with_ymd_and_hms(2025, 12, 24, 14, 0, 0).unwrap()));
+        assert!(!calendar.includes(Utc.with_ymd_and_hms(2025, 12, 25, 14, 0, 0).unwrap()));
+
+        // Compiled schedules are combined the same way.
+        let compiled = Calendar::from(schedule("0 0 14 * * Mon-Fri").compile()) - holidays.clone();
+        assert_eq!(moments(&compiled), moments(&calendar));
+
+        // Schedules exclude their moments, e.g. every second of the weekends.
+        let calendar = Calendar::from(schedule("0 0 14 * * *")) - schedule("* * * * * Sat,Sun");
+        assert_eq!(moments(&calendar), moments(&weekdays));
+
+        // Unions and intersections.
+        let calendar = Calendar::from(schedule("0 0 9 * * *")) | schedule("0 0 17 * * *");
+        assert_eq!(
+            moments(&calendar),
+            moments(&schedule("0 0 9,17 * * *").into())
+        );
+        let calendar = Calendar::from(schedule("0 0 9 1 * *")) & schedule("0 0 9 * * Mon");
+        assert_eq!(
+            moments(&calendar),
+            moments(&schedule("0 0 9 1 * Mon").into())
+        );
+        let calendar = Calendar::from(schedule("0 0 * * * *")) & schedule("0 */20 * * * *");
+        assert_eq!(moments(&calendar), moments(&schedule("0 0 * * * *").into()));
+        let calendar = Calendar::from(schedule("0 0 9 * * Mon")) & schedule("0 0 9 * * Tue");
+        assert_eq!(calendar.next_after(&start), None);
+
+        // Excluded dates are skipped at once, even from a schedule firing every second.
+        let calendar = Calendar::from(schedule("* * * * * *")) - holidays.clone();
+        let eve = Utc.with_ymd_and_hms(2025, 12, 24, 23, 59, 59).unwrap();
+        assert_eq!(
+            calendar.next_after(&eve),
+            Some(Utc.with_ymd_and_hms(2025, 12, 26, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            Calendar::from(holidays.clone()).next_after(&start),
+            Some(Utc.with_ymd_and_hms(2025, 12, 25, 0, 0, 0).unwrap())
+        );
+
+        // Dates start and end in their timezone.
+        let new_york = Tz::from_name("America/New_York");
+        let calendar = Calendar::from(schedule("0 0 * * * *")) - holidays.in_timezone(new_york);
+        let after = Utc.with_ymd_and_hms(2025, 12, 25, 3, 30, 0).unwrap();
+        let next: Vec<_> = calendar.after(&after).take(2).collect();
+        assert_eq!(
+            next,
+            [
+                Utc.with_ymd_and_hms(2025, 12, 25, 4, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 12, 26, 5, 0, 0).unwrap(),
+            ]
+        );
+    }
 }
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use anchor_lang::{InstructionData, ToAccountMetas};
 use log::info;
 use sablier_network_program::state::Worker;
-use sablier_thread_program::state::{Trigger, VersionedThread};
+use sablier_thread_program::state::{Trigger, TriggerCondition, VersionedThread};
 use sablier_utils::{pyth::get_oracle_key, thread::PAYER_PUBKEY};
 use solana_account_decoder::UiAccountEncoding;
 use solana_client::{
@@ -13,13 +13,17 @@ use solana_client::{
 };
 use solana_sdk::{
     account::Account,
+    address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
     commitment_config::CommitmentConfig,
     compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
     instruction::{AccountMeta, Instruction},
+    message::{v0, VersionedMessage},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
-    transaction::Transaction,
+    system_program, sysvar,
+    transaction::VersionedTransaction,
 };
 
 use crate::error::PluginError;
@@ -40,7 +44,7 @@ pub async fn build_thread_exec_tx(
     thread: VersionedThread,
     thread_pubkey: Pubkey,
     worker_id: u64,
-) -> Result<Option<Transaction>, PluginError> {
+) -> Result<Option<VersionedTransaction>, PluginError> {
     // Grab the thread and relevant data.
     let now = std::time::Instant::now();
     let blockhash = client.get_latest_blockhash().await?;
@@ -64,20 +68,36 @@ pub async fn build_thread_exec_tx(
         )
     };
 
+    // Compile the transaction with the thread's address lookup tables, if any.
+    let address_lookup_tables =
+        fetch_address_lookup_tables(&client, &thread.extension().address_lookup_tables).await?;
+
     // Simulate the transaction and pack as many instructions as possible until we hit mem/cpu limits.
-    // TODO Migrate to versioned transactions.
-    let mut ixs: Vec<Instruction> = vec![
-        ComputeBudgetInstruction::set_compute_unit_limit(TRANSACTION_COMPUTE_UNIT_LIMIT),
-        first_instruction,
-    ];
+    // Threads with a compute unit price pay for a priority fee, reimbursed to the signatory.
+    let mut ixs: Vec<Instruction> = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+        TRANSACTION_COMPUTE_UNIT_LIMIT,
+    )];
+    let compute_unit_price = thread.extension().compute_unit_price;
+    if compute_unit_price > 0 {
+        ixs.push(ComputeBudgetInstruction::set_compute_unit_price(
+            compute_unit_price,
+        ));
+    }
+    // Threads created before the current layout are migrated before being run.
+    if thread.needs_migration() {
+        ixs.push(build_migrate_ix(thread_pubkey, signatory_pubkey));
+    }
+    ixs.push(first_instruction);
     let mut successful_ixs: Vec<Instruction> = vec![];
     let mut units_consumed: Option<u64> = None;
     loop {
-        let mut sim_tx = Transaction::new_with_payer(&ixs, Some(&signatory_pubkey));
-        sim_tx.sign(&[payer], blockhash);
+        let Some(sim_tx) = build_tx(&ixs, payer, &address_lookup_tables, blockhash) else {
+            info!("The transaction cannot be compiled with the thread's address lookup tables.");
+            break;
+        };
 
         // Exit early if the transaction exceeds the size limit.
-        if sim_tx.message_data().len() > TRANSACTION_MESSAGE_SIZE_LIMIT {
+        if sim_tx.message.serialize().len() > TRANSACTION_MESSAGE_SIZE_LIMIT {
             info!("The transaction is too big to be send in one shot.");
             break;
         }
@@ -188,8 +208,9 @@ pub async fn build_thread_exec_tx(
     }
 
     // Build and return the signed transaction.
-    let mut tx = Transaction::new_with_payer(&successful_ixs, Some(&signatory_pubkey));
-    tx.sign(&[payer], blockhash);
+    let Some(tx) = build_tx(&successful_ixs, payer, &address_lookup_tables, blockhash) else {
+        return Ok(None);
+    };
     info!(
         "slot: {:?} thread: {:?} sim_duration: {:?} instruction_count: {:?} compute_units: {:?} tx_sig: {:?}",
         slot,
@@ -202,6 +223,54 @@ pub async fn build_thread_exec_tx(
     Ok(Some(tx))
 }
 
+/// Fetches the address lookup tables of a thread, skipping the accounts which are not lookup tables.
+async fn fetch_address_lookup_tables(
+    client: &RpcClient,
+    addresses: &[Pubkey],
+) -> Result<Vec<AddressLookupTableAccount>, PluginError> {
+    if addresses.is_empty() {
+        return Ok(vec![]);
+    }
+    let accounts = client.get_multiple_accounts(addresses).await?;
+    Ok(addresses
+        .iter()
+        .zip(accounts)
+        .filter_map(|(address, account)| {
+            let account = account?;
+            let table = AddressLookupTable::deserialize(&account.data).ok()?;
+            Some(AddressLookupTableAccount {
+                key: *address,
+                addresses: table.addresses.to_vec(),
+            })
+        })
+        .collect())
+}
+
+/// Builds a signed v0 transaction, looking up its accounts in the address lookup tables.
+fn build_tx(
+    ixs: &[Instruction],
+    payer: &Keypair,
+    address_lookup_tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Option<VersionedTransaction> {
+    let message =
+        v0::Message::try_compile(&payer.pubkey(), ixs, address_lookup_tables, blockhash).ok()?;
+    VersionedTransaction::try_new(VersionedMessage::V0(message), &[payer]).ok()
+}
+
+fn build_migrate_ix(thread_pubkey: Pubkey, signatory_pubkey: Pubkey) -> Instruction {
+    Instruction {
+        program_id: sablier_thread_program::ID,
+        accounts: sablier_thread_program::accounts::ThreadMigrate {
+            payer: signatory_pubkey,
+            system_program: system_program::ID,
+            thread: thread_pubkey,
+        }
+        .to_account_metas(Some(false)),
+        data: sablier_thread_program::instruction::ThreadMigrate {}.data(),
+    }
+}
+
 fn build_kickoff_ix(
     thread: VersionedThread,
     thread_pubkey: Pubkey,
@@ -210,7 +279,7 @@ fn build_kickoff_ix(
 ) -> Instruction {
     // Build the instruction.
     let mut kickoff_ix = match thread {
-        VersionedThread::V1(_) => Instruction {
+        VersionedThread::V1(_) | VersionedThread::V2(_) => Instruction {
             program_id: sablier_thread_program::ID,
             accounts: sablier_thread_program::accounts::ThreadKickoff {
                 signatory: signatory_pubkey,
@@ -223,7 +292,8 @@ fn build_kickoff_ix(
     };
 
     // If the thread's trigger is account-based, inject the triggering account.
-    match thread.trigger() {
+    let trigger = thread.trigger();
+    match trigger.primary().clone() {
         Trigger::Account {
             address,
             offset: _,
@@ -233,6 +303,23 @@ fn build_kickoff_ix(
             is_signer: false,
             is_writable: false,
         }),
+        Trigger::AccountValue { address, .. }
+        | Trigger::TokenBalance { address, .. }
+        | Trigger::Oracle { feed: address, .. }
+        | Trigger::Chain {
+            upstream: address, ..
+        } => kickoff_ix.accounts.push(AccountMeta {
+            pubkey: address,
+            is_signer: false,
+            is_writable: false,
+        }),
+        Trigger::PythPrice {
+            feed_id, shard_id, ..
+        } => kickoff_ix.accounts.push(AccountMeta {
+            pubkey: get_oracle_key(shard_id, feed_id),
+            is_signer: false,
+            is_writable: false,
+        }),
         Trigger::Pyth {
             feed_id,
             equality: _,
@@ -248,6 +335,30 @@ fn build_kickoff_ix(
         _ => {}
     }
 
+    // Inject the accounts read by the conditions of compound triggers.
+    for condition in trigger.conditions() {
+        let pubkey = match condition {
+            TriggerCondition::Pyth {
+                feed_id, shard_id, ..
+            } => get_oracle_key(*shard_id, *feed_id),
+            TriggerCondition::AccountValue { address, .. }
+            | TriggerCondition::TokenBalance { address, .. } => *address,
+            TriggerCondition::NotBefore { .. } | TriggerCondition::NotAfter { .. } => continue,
+        };
+        kickoff_ix.accounts.push(AccountMeta {
+            pubkey,
+            is_signer: false,
+            is_writable: false,
+        });
+    }
+
+    // Let the thread program read the priority fee of the transaction to reimburse it.
+    if thread.extension().compute_unit_price > 0 {
+        kickoff_ix
+            .accounts
+            .push(AccountMeta::new_readonly(sysvar::instructions::ID, false));
+    }
+
     kickoff_ix
 }
 
@@ -259,7 +370,7 @@ fn build_exec_ix(
 ) -> Instruction {
     // Build the instruction.
     let mut exec_ix = match thread {
-        VersionedThread::V1(_) => Instruction {
+        VersionedThread::V1(_) | VersionedThread::V2(_) => Instruction {
             program_id: sablier_thread_program::ID,
             accounts: sablier_thread_program::accounts::ThreadExec {
                 fee: sablier_network_program::state::Fee::pubkey(worker_pubkey),
@@ -294,5 +405,12 @@ fn build_exec_ix(
         }
     }
 
+    // Let the thread program read the priority fee of the transaction to reimburse it.
+    if thread.extension().compute_unit_price > 0 {
+        exec_ix
+            .accounts
+            .push(AccountMeta::new_readonly(sysvar::instructions::ID, false));
+    }
+
     exec_ix
 }
@@ -1,6 +1,11 @@
 use anchor_lang::{AccountDeserialize, Discriminator};
-use sablier_thread_program::state::{Thread, VersionedThread};
-use sablier_utils::pyth::{self, PriceFeedMessage, PriceUpdateV2};
+use sablier_thread_program::state::{Thread, ThreadV2, VersionedThread};
+use sablier_utils::{
+    oracle::OraclePrice,
+    pyth::{self, PriceFeedMessage, PriceUpdateV2},
+    switchboard::PullFeed,
+    thread::OracleProvider,
+};
 use solana_geyser_plugin_interface::geyser_plugin_interface::ReplicaAccountInfoVersions;
 use solana_sdk::{clock::Clock, pubkey::Pubkey, sysvar};
 
@@ -10,6 +15,8 @@ use crate::error::PluginError;
 pub struct AccountUpdate {
     pub key: Pubkey,
     pub event: Option<AccountUpdateEvent>,
+    /// The raw account data, only kept for accounts watched by value triggers.
+    pub data: Option<Vec<u8>>,
 }
 
 #[derive(Debug)]
@@ -17,10 +24,12 @@ pub enum AccountUpdateEvent {
     Clock { clock: Clock },
     Thread { thread: Box<VersionedThread> },
     PriceFeed { price_feed: PriceFeedMessage },
+    OraclePrice { price: OraclePrice },
 }
 
-impl<'a> From<ReplicaAccountInfoVersions<'a>> for AccountUpdate {
-    fn from(value: ReplicaAccountInfoVersions<'a>) -> Self {
+impl AccountUpdate {
+    /// Parses an account update, keeping a copy of the raw data if `keep_data` returns true for its key.
+    pub fn new(value: ReplicaAccountInfoVersions<'_>, keep_data: impl Fn(&Pubkey) -> bool) -> Self {
         let (key, owner, data) = match value {
             ReplicaAccountInfoVersions::V0_0_1(acc) => (acc.pubkey, acc.owner, acc.data),
             ReplicaAccountInfoVersions::V0_0_2(acc) => (acc.pubkey, acc.owner, acc.data),
@@ -32,8 +41,9 @@ impl<'a> From<ReplicaAccountInfoVersions<'a>> for AccountUpdate {
         let key = Pubkey::try_from(key).unwrap_or_default();
 
         let event = parse_event(key, owner, data).unwrap_or_default();
+        let data = keep_data(&key).then(|| data.to_vec());
 
-        AccountUpdate { key, event }
+        AccountUpdate { key, event, data }
     }
 }
 
@@ -50,9 +60,9 @@ fn parse_event(
 
     if owner == sablier_thread_program::ID && data.len() > 8 {
         let d = &data[..8];
-        if d == Thread::discriminator() {
+        if d == Thread::discriminator() || d == ThreadV2::discriminator() {
             return Ok(Some(AccountUpdateEvent::Thread {
-                thread: Box::new(VersionedThread::V1(Thread::try_deserialize(&mut data)?)),
+                thread: Box::new(VersionedThread::try_deserialize(&mut data)?),
             }));
         }
     }
@@ -63,5 +73,14 @@ fn parse_event(
         }));
     }
 
+    if PullFeed::is_owned_by(&owner) {
+        return Ok(OraclePrice::try_from_account(
+            OracleProvider::SwitchboardOnDemand,
+            &owner,
+            data,
+        )
+        .map(|price| AccountUpdateEvent::OraclePrice { price }));
+    }
+
     Ok(None)
 }
@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
 use log::info;
-use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::Transaction};
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
 use tokio::sync::RwLock;
 
 use crate::executors::tx::TransactionMetadata;
@@ -23,7 +23,7 @@ impl TransactionHistory {
         &self,
         slot: u64,
         thread_pubkey: Pubkey,
-        tx: &Transaction,
+        tx: &VersionedTransaction,
     ) -> bool {
         let r_state = self.0.read().await;
         if let Some(metadata) = r_state.get(&thread_pubkey) {
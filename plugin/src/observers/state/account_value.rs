@@ -0,0 +1,67 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Deref,
+    sync::PoisonError,
+};
+
+use sablier_utils::thread::{Endianness, Equality, ValueType};
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::RwLock;
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct AccountValueThread {
+    pub thread_pubkey: Pubkey,
+    pub offset: u64,
+    pub value_type: ValueType,
+    pub endianness: Endianness,
+    pub equality: Equality,
+    pub threshold: i128,
+    pub prior_value: Option<i128>,
+}
+
+/// The threads watching values of accounts, indexed by account.
+/// The watched accounts are mirrored behind a synchronous lock, so the plugin can check them while parsing updates.
+#[derive(Default)]
+pub struct AccountValueThreads(
+    RwLock<HashMap<Pubkey, HashSet<AccountValueThread>>>,
+    std::sync::RwLock<HashSet<Pubkey>>,
+);
+
+impl AccountValueThreads {
+    pub async fn add(&self, address: Pubkey, data: AccountValueThread) {
+        let mut w_state = self.0.write().await;
+        let mut w_watched = self.1.write().unwrap_or_else(PoisonError::into_inner);
+
+        // Drop the stale entry of this thread, its prior value may have changed.
+        w_state.retain(|watched, v| {
+            v.retain(|t| t.thread_pubkey != data.thread_pubkey);
+            if v.is_empty() {
+                w_watched.remove(watched);
+            }
+            !v.is_empty()
+        });
+        w_state
+            .entry(address)
+            .and_modify(|v| {
+                v.insert(data.to_owned());
+            })
+            .or_insert(HashSet::from([data]));
+        w_watched.insert(address);
+    }
+
+    /// Returns true if a thread is watching a value of this account.
+    pub fn watches(&self, account: &Pubkey) -> bool {
+        self.1
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(account)
+    }
+}
+
+impl Deref for AccountValueThreads {
+    type Target = RwLock<HashMap<Pubkey, HashSet<AccountValueThread>>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
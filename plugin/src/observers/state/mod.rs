@@ -1,15 +1,19 @@
 mod account;
+mod account_value;
 mod clock;
 mod cron;
 mod epoch;
 mod now;
+mod oracle;
 mod pyth;
 mod slot;
 
 pub use account::*;
+pub use account_value::*;
 pub use clock::*;
 pub use cron::*;
 pub use epoch::*;
 pub use now::*;
+pub use oracle::*;
 pub use pyth::*;
 pub use slot::*;
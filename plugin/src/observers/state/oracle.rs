@@ -0,0 +1,49 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Deref,
+};
+
+use sablier_utils::thread::Equality;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::RwLock;
+
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct OracleThread {
+    pub thread_pubkey: Pubkey,
+    pub equality: Equality,
+    pub limit: i64,
+    pub limit_exponent: i32,
+    pub max_age: u64,
+    pub prior_price: Option<i128>,
+}
+
+#[derive(Default)]
+pub struct OracleThreads(RwLock<HashMap<Pubkey, HashSet<OracleThread>>>);
+
+impl OracleThreads {
+    pub async fn add(&self, feed: Pubkey, data: OracleThread) {
+        let mut w_state = self.0.write().await;
+
+        // Drop any stale entry for this thread.
+        w_state.retain(|_, oracle_threads| {
+            oracle_threads
+                .retain(|oracle_thread| oracle_thread.thread_pubkey != data.thread_pubkey);
+            !oracle_threads.is_empty()
+        });
+
+        w_state
+            .entry(feed)
+            .and_modify(|v| {
+                v.insert(data.to_owned());
+            })
+            .or_insert(HashSet::from([data]));
+    }
+}
+
+impl Deref for OracleThreads {
+    type Target = RwLock<HashMap<Pubkey, HashSet<OracleThread>>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
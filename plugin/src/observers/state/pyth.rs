@@ -3,15 +3,21 @@ use std::{
     ops::Deref,
 };
 
-use sablier_utils::thread::Equality;
+use sablier_utils::thread::{Equality, PriceType};
 use solana_sdk::pubkey::Pubkey;
 use tokio::sync::RwLock;
 
 #[derive(Clone, Eq, Hash, PartialEq)]
 pub struct PythThread {
     pub thread_pubkey: Pubkey,
+    pub price_type: PriceType,
     pub equality: Equality,
     pub limit: i64,
+    /// The exponent of the limit.
+    pub limit_exponent: i32,
+    pub max_age: u64,
+    pub max_confidence_bps: Option<u64>,
+    pub prior_price: Option<i64>,
 }
 
 #[derive(Default)]
@@ -19,14 +25,26 @@ pub struct PythThreads(RwLock<HashMap<Pubkey, HashSet<PythThread>>>);
 
 impl PythThreads {
     pub async fn add(&self, price_key: Pubkey, data: PythThread) {
+        self.replace(data.thread_pubkey, [(price_key, data)]).await;
+    }
+
+    /// Replaces the entries of a thread, which may compare several prices.
+    pub async fn replace(
+        &self,
+        thread_pubkey: Pubkey,
+        entries: impl IntoIterator<Item = (Pubkey, PythThread)>,
+    ) {
         let mut w_state = self.0.write().await;
 
-        w_state
-            .entry(price_key)
-            .and_modify(|v| {
-                v.insert(data.to_owned());
-            })
-            .or_insert(HashSet::from([data]));
+        // Drop any stale entry for this thread.
+        w_state.retain(|_, pyth_threads| {
+            pyth_threads.retain(|pyth_thread| pyth_thread.thread_pubkey != thread_pubkey);
+            !pyth_threads.is_empty()
+        });
+
+        for (price_key, data) in entries {
+            w_state.entry(price_key).or_default().insert(data);
+        }
     }
 }
 
@@ -1,22 +1,32 @@
 use std::{
     collections::HashSet,
     fmt::Debug,
-    str::FromStr,
     sync::{atomic::AtomicU64, Arc},
 };
 
 use chrono::DateTime;
 use log::info;
-use sablier_cron::Schedule;
-use sablier_thread_program::state::{Equality, Trigger, TriggerContext, VersionedThread};
-use sablier_utils::pyth::{get_oracle_key, PriceFeedMessage};
+use sablier_cron::Calendar;
+use sablier_thread_program::{
+    constants::PYTH_STALENESS_THRESHOLD,
+    state::{PriceType, Trigger, TriggerCondition, TriggerContext, VersionedThread},
+};
+use sablier_utils::{
+    oracle::OraclePrice,
+    pyth::{get_oracle_key, PriceFeedMessage},
+    thread::{Endianness, ValueType},
+    token::AMOUNT_OFFSET,
+};
 use solana_sdk::{clock::Clock, pubkey::Pubkey};
 
-use crate::{error::PluginError, observers::state::PythThread};
+use crate::{
+    error::PluginError,
+    observers::state::{AccountValueThread, OracleThread, PythThread},
+};
 
 use super::state::{
-    AccountThreads, Clocks, CronThreads, EpochThreads, NowThreads, PythThreads, SlotThreads,
-    UpdatedAccounts,
+    AccountThreads, AccountValueThreads, Clocks, CronThreads, EpochThreads, NowThreads,
+    OracleThreads, PythThreads, SlotThreads, UpdatedAccounts,
 };
 
 #[derive(Default)]
@@ -47,6 +57,21 @@ pub struct ThreadObserver {
     // The set of threads with a pyth trigger.
     pub pyth_threads: PythThreads,
 
+    // The set of threads with a compound trigger whose conditions compare pyth prices.
+    pub pyth_condition_threads: PythThreads,
+
+    // The set of threads with an oracle trigger.
+    // Map from feed account pubkeys to the set of threads comparing its price.
+    pub oracle_threads: OracleThreads,
+
+    // The set of threads with a chain trigger.
+    // Map from upstream thread pubkeys to the set of threads waiting for them to complete a cycle.
+    pub chain_threads: AccountThreads,
+
+    // The set of threads with an account value trigger.
+    // Map from account pubkeys to the set of threads watching a value of that account.
+    pub account_value_threads: AccountValueThreads,
+
     // The set of accounts that have updated.
     pub updated_accounts: UpdatedAccounts,
 }
@@ -134,37 +159,130 @@ impl ThreadObserver {
         }
     }
 
+    /// Move all threads whose value crossed or was released from the threshold in this account data into the executable set.
+    pub async fn observe_account_value(self: Arc<Self>, account_pubkey: Pubkey, data: Vec<u8>) {
+        let r_account_value_threads = self.account_value_threads.read().await;
+        if let Some(account_value_threads) = r_account_value_threads.get(&account_pubkey) {
+            for account_value_thread in account_value_threads {
+                let value = account_value_thread.value_type.read(
+                    &data,
+                    account_value_thread.offset,
+                    account_value_thread.endianness,
+                );
+                if let Some(value) = value {
+                    let AccountValueThread {
+                        equality,
+                        threshold,
+                        prior_value,
+                        ..
+                    } = account_value_thread;
+                    if equality.is_crossed(value, *threshold, *prior_value)
+                        || equality.is_released(value, *threshold, *prior_value)
+                    {
+                        self.now_threads
+                            .add(account_value_thread.thread_pubkey)
+                            .await;
+                    }
+                }
+            }
+        }
+        drop(r_account_value_threads);
+    }
+
     pub async fn observe_price_feed(
         self: Arc<Self>,
         account_pubkey: Pubkey,
         price_feed: PriceFeedMessage,
     ) {
-        let r_pyth_threads = self.pyth_threads.read().await;
+        let now = self.latest_unix_timestamp().await;
+        for pyth_threads in [&self.pyth_threads, &self.pyth_condition_threads] {
+            self.observe_pyth_threads(pyth_threads, account_pubkey, &price_feed, now)
+                .await;
+        }
+    }
+
+    /// Move the threads of an index whose limit is met by this price feed into the executable set.
+    async fn observe_pyth_threads(
+        &self,
+        pyth_threads: &PythThreads,
+        account_pubkey: Pubkey,
+        price_feed: &PriceFeedMessage,
+        now: Option<i64>,
+    ) {
+        let r_pyth_threads = pyth_threads.read().await;
         if let Some(pyth_threads) = r_pyth_threads.get(&account_pubkey) {
             for pyth_thread in pyth_threads {
-                match pyth_thread.equality {
-                    Equality::GreaterThanOrEqual => {
-                        if price_feed.price.ge(&pyth_thread.limit) {
-                            self.now_threads.add(pyth_thread.thread_pubkey).await;
-                        }
-                    }
-                    Equality::LessThanOrEqual => {
-                        if price_feed.price.le(&pyth_thread.limit) {
-                            self.now_threads.add(pyth_thread.thread_pubkey).await;
-                        }
-                    }
+                let is_fresh = now.map_or(true, |now| {
+                    price_feed
+                        .publish_time
+                        .saturating_add(pyth_thread.max_age as i64)
+                        .ge(&now)
+                });
+                if is_fresh
+                    && price_feed.meets_limit(
+                        pyth_thread.price_type,
+                        pyth_thread.max_confidence_bps,
+                        &pyth_thread.equality,
+                        pyth_thread.limit,
+                        pyth_thread.limit_exponent,
+                        pyth_thread.prior_price,
+                    )
+                {
+                    self.now_threads.add(pyth_thread.thread_pubkey).await;
                 }
             }
         }
         drop(r_pyth_threads);
     }
 
+    /// Move all threads whose limit is met by this oracle price into the executable set.
+    pub async fn observe_oracle_price(self: Arc<Self>, account_pubkey: Pubkey, price: OraclePrice) {
+        let now = self.latest_unix_timestamp().await;
+        let r_oracle_threads = self.oracle_threads.read().await;
+        if let Some(oracle_threads) = r_oracle_threads.get(&account_pubkey) {
+            for oracle_thread in oracle_threads {
+                let is_fresh = now.map_or(true, |now| price.is_fresh(now, oracle_thread.max_age));
+                if is_fresh
+                    && price.meets_limit(
+                        &oracle_thread.equality,
+                        oracle_thread.limit,
+                        oracle_thread.limit_exponent,
+                        oracle_thread.prior_price,
+                    )
+                {
+                    self.now_threads.add(oracle_thread.thread_pubkey).await;
+                }
+            }
+        }
+        drop(r_oracle_threads);
+    }
+
+    /// Returns the latest observed cluster time, used to check the freshness of oracle prices.
+    async fn latest_unix_timestamp(&self) -> Option<i64> {
+        let r_clocks = self.clocks.read().await;
+        r_clocks
+            .iter()
+            .max_by_key(|(slot, _clock)| *slot)
+            .map(|(_slot, clock)| clock.unix_timestamp)
+    }
+
     pub async fn observe_thread(
         self: Arc<Self>,
         thread: VersionedThread,
         thread_pubkey: Pubkey,
         slot: u64,
     ) -> Result<(), PluginError> {
+        // If the thread completed an execution cycle, attempt to execute the threads chained to it.
+        if thread.completed_cycle_at(false).is_some() {
+            let r_chain_threads = self.chain_threads.read().await;
+            if let Some(thread_pubkeys) = r_chain_threads.get(&thread_pubkey) {
+                for dependent_pubkey in thread_pubkeys {
+                    self.now_threads.add(*dependent_pubkey).await;
+                }
+            }
+            drop(r_chain_threads);
+        }
+
         // If the thread is paused, just return without indexing
         if thread.paused() {
             return Ok(());
@@ -175,8 +293,32 @@ impl ThreadObserver {
             // If the thread has a next instruction, index it as executable.
             self.now_threads.add(thread_pubkey).await;
         } else {
+            // If the thread has expired, attempt to kick it off right away to close it.
+            let unix_timestamp = self.latest_unix_timestamp().await.unwrap_or(i64::MIN);
+            if thread.is_expired(unix_timestamp) {
+                self.now_threads.add(thread_pubkey).await;
+                return Ok(());
+            }
+
+            // If the thread's execution window has not started yet, index it to the start of the window.
+            let lifetime = thread.extension().lifetime;
+            if lifetime.is_before_window(unix_timestamp) {
+                if let Some(not_before) = lifetime.not_before {
+                    self.cron_threads.add(not_before, thread_pubkey).await;
+                }
+                return Ok(());
+            }
+
+            // If the thread's execution window ends, index it to the end of the window to close it.
+            if let Some(not_after) = lifetime.not_after {
+                self.cron_threads
+                    .add(not_after.saturating_add(1), thread_pubkey)
+                    .await;
+            }
+
             // Otherwise, index the thread according to its trigger type.
-            match thread.trigger() {
+            // Compound threads are indexed by their primary trigger, their conditions are verified in simulation.
+            match thread.trigger().primary().clone() {
                 Trigger::Account { address, .. } => {
                     // Index the thread by its trigger's account pubkey.
                     self.account_threads.add(address, thread_pubkey).await;
@@ -185,18 +327,50 @@ impl ThreadObserver {
                     // Thus, we should attempt to execute these threads right away without for an account update.
                     self.now_threads.add(thread_pubkey).await;
                 }
+                Trigger::TokenBalance {
+                    address,
+                    equality,
+                    threshold,
+                } => {
+                    let prior_value = match thread.exec_context() {
+                        None => None,
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::TokenBalance { amount } => Some(amount as i128),
+                            _ => return Err(PluginError::InvalidExecContext),
+                        },
+                    };
+
+                    // Index the thread by the amount of its trigger's token account.
+                    let account_value_thread = AccountValueThread {
+                        thread_pubkey,
+                        offset: AMOUNT_OFFSET as u64,
+                        value_type: ValueType::U64,
+                        endianness: Endianness::Little,
+                        equality,
+                        threshold: threshold as i128,
+                        prior_value,
+                    };
+                    self.account_value_threads
+                        .add(address, account_value_thread)
+                        .await;
+                }
                 Trigger::Cron { schedule, .. } => {
                     // Find a reference timestamp for calculating the thread's upcoming target time.
                     let reference_timestamp = match thread.exec_context() {
                         None => thread.created_at().unix_timestamp,
                         Some(exec_context) => match exec_context.trigger_context {
-                            TriggerContext::Cron { started_at } => started_at,
+                            TriggerContext::Cron { started_at, .. } => started_at,
                             _ => return Err(PluginError::InvalidExecContext),
                         },
                     };
 
-                    // Index the thread to its target timestamp
-                    match next_moment(reference_timestamp, schedule) {
+                    // Index the thread to its target timestamp.
+                    // Missed moments are due right away, the kickoff coalesces them according to the thread's catch-up policy.
+                    let calendar = thread.extension().cron_calendar(&schedule, &thread_pubkey);
+                    match calendar
+                        .ok()
+                        .and_then(|calendar| next_moment(reference_timestamp, &calendar))
+                    {
                         None => {} // The thread does not have any upcoming scheduled target time
                         Some(target_timestamp) => {
                             self.cron_threads.add(target_timestamp, thread_pubkey).await
@@ -220,14 +394,56 @@ impl ThreadObserver {
                     equality,
                     limit,
                 } => {
+                    let prior_price = match thread.exec_context() {
+                        None => None,
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::Pyth { price } => Some(price),
+                            _ => return Err(PluginError::InvalidExecContext),
+                        },
+                    };
                     let pyth_thread = PythThread {
                         thread_pubkey,
+                        price_type: PriceType::Spot,
                         equality,
                         limit,
+                        limit_exponent: 0,
+                        max_age: PYTH_STALENESS_THRESHOLD,
+                        max_confidence_bps: None,
+                        prior_price,
                     };
                     let price_pubkey = get_oracle_key(0, feed_id);
                     self.pyth_threads.add(price_pubkey, pyth_thread).await;
                 }
+                Trigger::PythPrice {
+                    feed_id,
+                    shard_id,
+                    price_type,
+                    equality,
+                    limit,
+                    limit_exponent,
+                    max_age,
+                    max_confidence_bps,
+                } => {
+                    let prior_price = match thread.exec_context() {
+                        None => None,
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::Pyth { price } => Some(price),
+                            _ => return Err(PluginError::InvalidExecContext),
+                        },
+                    };
+                    let pyth_thread = PythThread {
+                        thread_pubkey,
+                        price_type,
+                        equality,
+                        limit,
+                        limit_exponent,
+                        max_age,
+                        max_confidence_bps,
+                        prior_price,
+                    };
+                    let price_pubkey = get_oracle_key(shard_id, feed_id);
+                    self.pyth_threads.add(price_pubkey, pyth_thread).await;
+                }
                 Trigger::Periodic { delay } => {
                     // Find a reference timestamp for calculating the thread's upcoming target time.
                     let reference_timestamp = match thread.exec_context() {
@@ -241,6 +457,145 @@ impl ThreadObserver {
                     let next_moment = reference_timestamp + delay as i64;
                     self.cron_threads.add(next_moment, thread_pubkey).await;
                 }
+                Trigger::AccountValue {
+                    address,
+                    offset,
+                    value_type,
+                    endianness,
+                    equality,
+                    threshold,
+                } => {
+                    let prior_value = match thread.exec_context() {
+                        None => None,
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::AccountValue { value } => Some(value),
+                            _ => return Err(PluginError::InvalidExecContext),
+                        },
+                    };
+
+                    // Index the thread by its trigger's account pubkey.
+                    let account_value_thread = AccountValueThread {
+                        thread_pubkey,
+                        offset,
+                        value_type,
+                        endianness,
+                        equality,
+                        threshold,
+                        prior_value,
+                    };
+                    self.account_value_threads
+                        .add(address, account_value_thread)
+                        .await;
+                }
+                Trigger::Oracle {
+                    provider: _,
+                    feed,
+                    equality,
+                    limit,
+                    limit_exponent,
+                    max_age,
+                } => {
+                    let prior_price = match thread.exec_context() {
+                        None => None,
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::Oracle { price } => Some(price),
+                            _ => return Err(PluginError::InvalidExecContext),
+                        },
+                    };
+                    let oracle_thread = OracleThread {
+                        thread_pubkey,
+                        equality,
+                        limit,
+                        limit_exponent,
+                        max_age,
+                        prior_price,
+                    };
+                    self.oracle_threads.add(feed, oracle_thread).await;
+                }
+                Trigger::Chain { upstream, .. } => {
+                    // Index the thread by its upstream thread pubkey.
+                    self.chain_threads.add(upstream, thread_pubkey).await;
+
+                    // The upstream thread might have already completed a cycle, so attempt to execute the thread right away.
+                    self.now_threads.add(thread_pubkey).await;
+                }
+                Trigger::Signal { .. } => {
+                    // A pending signal is the cue to kick off the thread.
+                    if thread.exec_context().is_some_and(|exec_context| {
+                        exec_context.trigger_context.has_pending_signal()
+                    }) {
+                        self.now_threads.add(thread_pubkey).await;
+                    }
+                }
+                Trigger::SlotInterval {
+                    interval,
+                    start_slot,
+                    ..
+                } => {
+                    // Find the threshold slot of the thread's next kickoff.
+                    let threshold_slot = match thread.exec_context() {
+                        None => {
+                            start_slot.unwrap_or(thread.created_at().slot.saturating_add(interval))
+                        }
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::SlotInterval { started_at } => {
+                                started_at.saturating_add(interval)
+                            }
+                            _ => return Err(PluginError::InvalidExecContext),
+                        },
+                    };
+                    self.slot_threads.add(threshold_slot, thread_pubkey).await;
+                }
+                Trigger::Compound { .. } => {
+                    // Nested compound triggers are rejected by the thread program.
+                }
+            }
+
+            // Recurring primaries skip the moments at which the conditions don't hold.
+            // Other primaries stay due until they do, so index the thread by its conditions as well.
+            if !matches!(
+                thread.trigger().primary(),
+                Trigger::Cron { .. } | Trigger::Periodic { .. } | Trigger::SlotInterval { .. }
+            ) {
+                let mut pyth_conditions = vec![];
+                for condition in thread.trigger().conditions().iter().cloned() {
+                    match condition {
+                        TriggerCondition::NotBefore { unix_ts } => {
+                            self.cron_threads.add(unix_ts, thread_pubkey).await
+                        }
+                        TriggerCondition::NotAfter { .. } => {}
+                        TriggerCondition::Pyth {
+                            feed_id,
+                            shard_id,
+                            price_type,
+                            equality,
+                            limit,
+                            limit_exponent,
+                            max_age,
+                            max_confidence_bps,
+                        } => {
+                            // Index the thread by the price it compares, the same way the kickoff does.
+                            let pyth_thread = PythThread {
+                                thread_pubkey,
+                                price_type,
+                                equality,
+                                limit,
+                                limit_exponent,
+                                max_age,
+                                max_confidence_bps,
+                                prior_price: None,
+                            };
+                            pyth_conditions.push((get_oracle_key(shard_id, feed_id), pyth_thread));
+                        }
+                        TriggerCondition::AccountValue { address, .. }
+                        | TriggerCondition::TokenBalance { address, .. } => {
+                            self.account_threads.add(address, thread_pubkey).await;
+                        }
+                    }
+                }
+                self.pyth_condition_threads
+                    .replace(thread_pubkey, pyth_conditions)
+                    .await;
             }
         }
 
@@ -254,12 +609,9 @@ impl Debug for ThreadObserver {
     }
 }
 
-fn next_moment(after: i64, schedule: String) -> Option<i64> {
-    match Schedule::from_str(&schedule) {
-        Err(_) => None,
-        Ok(schedule) => schedule
-            .next_after(&DateTime::from_timestamp(after, 0).unwrap())
-            .take()
-            .map(|datetime| datetime.timestamp()),
-    }
+/// Returns the next moment of the schedule of a thread, without its excluded dates and moments.
+fn next_moment(after: i64, calendar: &Calendar) -> Option<i64> {
+    calendar
+        .next_after(&DateTime::from_timestamp(after, 0)?)
+        .map(|datetime| datetime.timestamp())
 }
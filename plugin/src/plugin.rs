@@ -57,7 +57,13 @@ impl GeyserPlugin for SablierPlugin {
         is_startup: bool,
     ) -> PluginResult<()> {
         // Parse account info.
-        let account_update = AccountUpdate::from(account);
+        let account_update = AccountUpdate::new(account, |key| {
+            self.inner
+                .observers
+                .thread
+                .account_value_threads
+                .watches(key)
+        });
 
         // Process event on tokio task.
         self.inner.clone().spawn(|inner| async move {
@@ -70,6 +76,16 @@ impl GeyserPlugin for SablierPlugin {
                     .clone()
                     .observe_account(account_update.key, slot)
                     .await;
+
+                // Check the conditions of threads watching a value of this account.
+                if let Some(data) = account_update.data {
+                    inner
+                        .observers
+                        .thread
+                        .clone()
+                        .observe_account_value(account_update.key, data)
+                        .await;
+                }
             }
 
             if let Some(event) = account_update.event {
@@ -88,6 +104,13 @@ impl GeyserPlugin for SablierPlugin {
                             .ok();
                     }
                     AccountUpdateEvent::PriceFeed { price_feed } => {
+                        // Pyth price updates also feed the threads with a Pyth oracle trigger.
+                        inner
+                            .observers
+                            .thread
+                            .clone()
+                            .observe_oracle_price(account_update.key, (&price_feed).into())
+                            .await;
                         inner
                             .observers
                             .thread
@@ -95,6 +118,14 @@ impl GeyserPlugin for SablierPlugin {
                             .observe_price_feed(account_update.key, price_feed)
                             .await;
                     }
+                    AccountUpdateEvent::OraclePrice { price } => {
+                        inner
+                            .observers
+                            .thread
+                            .clone()
+                            .observe_oracle_price(account_update.key, price)
+                            .await;
+                    }
                 }
             }
 
@@ -1,9 +1,5 @@
-use std::{
-    collections::hash_map::DefaultHasher,
-    hash::{Hash, Hasher},
-};
-
 use anchor_lang::{prelude::*, AnchorDeserialize};
+use sablier_utils::hash::stable_hash;
 
 use crate::constants::SEED_REGISTRY;
 
@@ -47,10 +43,8 @@ impl RegistryAccount for Account<'_, Registry> {
     }
 
     fn hash_nonce(&mut self) -> Result<()> {
-        let mut hasher = DefaultHasher::new();
-        Clock::get()?.slot.hash(&mut hasher);
-        self.nonce.hash(&mut hasher);
-        self.nonce = hasher.finish();
+        let slot = Clock::get()?.slot;
+        self.nonce = stable_hash(&[&slot.to_le_bytes(), &self.nonce.to_le_bytes()]);
         Ok(())
     }
 }
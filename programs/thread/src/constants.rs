@@ -15,6 +15,31 @@ pub const POOL_ID: u64 = 0;
 #[constant]
 pub const TRANSACTION_BASE_FEE_REIMBURSEMENT: u64 = 5_000;
 
-/// Static space for next_instruction field.
+/// Static space reserved for the next instruction of `Thread` accounts.
 #[constant]
 pub const NEXT_INSTRUCTION_SIZE: usize = 1232;
+
+/// The maximum age, in seconds, of the Pyth price updates read by "pyth" triggers.
+#[constant]
+pub const PYTH_STALENESS_THRESHOLD: u64 = 60;
+
+/// The maximum number of missed moments counted at the kickoff of a cron thread.
+/// The `max_catch_up` of cron threads must be lower.
+#[constant]
+pub const CRON_CATCH_UP_SCAN_LIMIT: u64 = 64;
+
+/// The length of the compiled cron schedules stored in threads.
+#[constant]
+pub const COMPILED_SCHEDULE_LEN: usize = sablier_cron::CompiledSchedule::LEN;
+
+/// The maximum number of dates excluded from the schedule of a cron thread.
+#[constant]
+pub const MAX_CRON_EXCLUDED_DATES: usize = 512;
+
+/// The maximum number of address lookup tables a thread may reference.
+#[constant]
+pub const MAX_ADDRESS_LOOKUP_TABLES: usize = 4;
+
+/// The maximum length, in bytes, of the name of a thread.
+#[constant]
+pub const MAX_THREAD_NAME_LEN: usize = 64;
@@ -51,4 +51,48 @@ pub enum SablierError {
     /// Thrown if the provided account is not a valid Thread account.
     #[msg("The provided thread account is not a valid Thread account")]
     InvalidThreadAccount,
+
+    /// Thrown if a trigger is malformed (e.g. a nested compound trigger).
+    #[msg("The trigger is invalid")]
+    InvalidTrigger,
+
+    /// Thrown if the signer of a signal is neither the thread authority nor the signaler of its signal trigger.
+    #[msg("The signer is not allowed to signal this thread")]
+    InvalidSignaler,
+
+    /// Thrown if a thread lifetime ends before it starts.
+    #[msg("The thread's execution window is empty")]
+    InvalidThreadLifetime,
+
+    /// Thrown if a thread cannot be kicked off because its lifetime has not started yet.
+    #[msg("The thread's execution window has not started")]
+    ThreadNotStarted,
+
+    /// Thrown if a thread authority attempts to set a cron catch-up above the maximum allowed value.
+    #[msg("Cron catch-ups cannot exceed the maximum allowed value")]
+    MaxCatchUpExceeded,
+
+    /// Thrown if a cron schedule cannot be parsed, or is not written in the thread's cron dialect.
+    #[msg("The cron schedule is invalid")]
+    InvalidSchedule,
+
+    /// Thrown if cron exclusions have unsorted or too many dates.
+    #[msg("The cron exclusions are invalid")]
+    InvalidCronExclusions,
+
+    /// Thrown if a thread authority attempts to reference more address lookup tables than allowed.
+    #[msg("Threads cannot reference more than the maximum number of address lookup tables")]
+    MaxAddressLookupTablesExceeded,
+
+    /// Thrown if a thread budget caps its spending over an empty window.
+    #[msg("The thread's budget window is empty")]
+    InvalidThreadBudget,
+
+    /// Thrown if a thread authority attempts to set a name longer than the maximum allowed length.
+    #[msg("The thread's name is too long")]
+    ThreadNameTooLong,
+
+    /// Thrown if a thread authority attempts to set a deprecated trigger.
+    #[msg("The trigger is deprecated")]
+    DeprecatedTrigger,
 }
@@ -2,9 +2,12 @@ use anchor_lang::{
     prelude::*,
     system_program::{transfer, Transfer},
 };
-use sablier_utils::thread::{SerializableInstruction, Trigger};
+use sablier_utils::{
+    account::AccountExtension,
+    thread::{SerializableInstruction, Trigger},
+};
 
-use crate::{constants::*, state::*};
+use crate::{constants::*, errors::*, state::*};
 
 /// Accounts required by the `thread_create` instruction.
 #[derive(Accounts)]
@@ -31,9 +34,9 @@ pub struct ThreadCreate<'info> {
         ],
         bump,
         payer = payer,
-        space = Thread::min_space(&instructions)?
+        space = ThreadV2::min_space(&instructions, &trigger)?
     )]
-    pub thread: Account<'info, Thread>,
+    pub thread: Account<'info, ThreadV2>,
 }
 
 pub fn handler(
@@ -50,6 +53,11 @@ pub fn handler(
     let system_program = &ctx.accounts.system_program;
     let thread = &mut ctx.accounts.thread;
 
+    // Verify the trigger is well-formed.
+    require!(trigger.is_valid(), SablierError::InvalidTrigger);
+    require!(!trigger.is_deprecated(), SablierError::DeprecatedTrigger);
+    let compiled_schedule = CronOptions::default().compile(&trigger, &thread.key())?;
+
     // Initialize the thread
     let bump = ctx.bumps.thread;
     thread.authority = authority.key();
@@ -64,6 +72,8 @@ pub fn handler(
     thread.paused = false;
     thread.rate_limit = u64::MAX;
     thread.trigger = trigger;
+    thread.extension = AccountExtension::default();
+    thread.extension.compiled_schedule = compiled_schedule;
 
     // Transfer SOL from payer to the thread.
     transfer(
@@ -28,7 +28,7 @@ pub fn handler(ctx: Context<ThreadDelete>) -> Result<()> {
     // First, must try to deserialize the thread.
 
     // Get either V1 or V2 thread - If the provided thread does not exist, print an error message and return Ok.
-    let thread = match Thread::try_deserialize_unchecked(&mut thread.data.borrow_mut().as_ref()) {
+    let thread = match VersionedThread::try_deserialize(&mut thread.data.borrow_mut().as_ref()) {
         Ok(t) => t,
         Err(_) => {
             msg!("Not a thread or account does not exist");
@@ -43,7 +43,7 @@ pub fn handler(ctx: Context<ThreadDelete>) -> Result<()> {
         let thread_key = ctx.accounts.thread.key;
 
         require!(
-            thread.authority.eq(authority_key) || authority_key.eq(thread_key),
+            thread.authority().eq(authority_key) || authority_key.eq(thread_key),
             SablierError::InvalidThreadAuthority
         );
 
@@ -63,13 +63,15 @@ pub fn handler(ctx: Context<ThreadDelete>) -> Result<()> {
             );
 
             // Verify the seed derivation
-            let default_vec = Vec::new();
-            let thread_bump = thread.bump.to_le_bytes();
+            let thread_authority = thread.authority();
+            let thread_id = thread.id();
+            let thread_domain = thread.domain().unwrap_or_default();
+            let thread_bump = thread.bump().to_le_bytes();
             let seed = [
                 SEED_THREAD,
-                thread.authority.as_ref(),
-                thread.id.as_slice(),
-                thread.domain.as_ref().unwrap_or(&default_vec).as_slice(),
+                thread_authority.as_ref(),
+                thread_id.as_slice(),
+                thread_domain.as_slice(),
                 thread_bump.as_ref(),
             ];
             let expected_thread_key = Pubkey::create_program_address(&seed, &crate::ID)
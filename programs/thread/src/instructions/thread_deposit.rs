@@ -0,0 +1,55 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{constants::*, state::*};
+
+/// Accounts required by the `thread_deposit` instruction.
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct ThreadDeposit<'info> {
+    /// The payer of the deposit.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The Solana system program.
+    pub system_program: Program<'info, System>,
+
+    /// The thread to deposit lamports into.
+    #[account(
+        mut,
+        seeds = [
+            SEED_THREAD,
+            thread.authority.as_ref(),
+            thread.id.as_slice(),
+            thread.domain.as_ref().unwrap_or(&Vec::new()).as_slice()
+        ],
+        bump = thread.bump,
+    )]
+    pub thread: Account<'info, ThreadV2>,
+}
+
+pub fn handler(ctx: Context<ThreadDeposit>, amount: u64) -> Result<()> {
+    // Get accounts
+    let payer = &ctx.accounts.payer;
+    let system_program = &ctx.accounts.system_program;
+    let thread = &mut ctx.accounts.thread;
+
+    // Realloc the thread account
+    thread.realloc_account()?;
+
+    // Transfer lamports from the payer to the thread.
+    transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: thread.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    Ok(())
+}
@@ -49,7 +49,7 @@ pub struct ThreadExec<'info> {
         constraint = thread.next_instruction.is_some(),
         constraint = thread.exec_context.is_some()
     )]
-    pub thread: Box<Account<'info, Thread>>,
+    pub thread: Box<Account<'info, ThreadV2>>,
 
     /// The worker.
     #[account(address = worker.pubkey())]
@@ -72,6 +72,56 @@ pub fn handler(ctx: Context<ThreadExec>) -> Result<()> {
         return Err(SablierError::RateLimitExeceeded.into());
     }
 
+    // Once the thread has passed the end of its execution window, refuse to execute its next instruction.
+    // Instead, replace it with the deletion of the thread, which is executed by the next exec.
+    let is_deletion = thread.next_instruction.as_ref().is_some_and(|ix| {
+        ix.program_id.eq(&crate::ID) && ix.data.eq(&crate::instruction::ThreadDelete {}.data())
+    });
+    if thread.is_past_window(clock.unix_timestamp) && !is_deletion {
+        let authority = thread.authority;
+        thread.next_instruction = Some(thread.delete_instruction(authority));
+        thread.realloc_account()?;
+
+        // Reimburse signatory for transaction fees.
+        let transaction_fee = thread.transaction_fee(ctx.remaining_accounts)?;
+        thread.sub_lamports(transaction_fee)?;
+        signatory.add_lamports(transaction_fee)?;
+        return Ok(());
+    }
+
+    // If paying for the exec would bring the balance of the thread below its reserve,
+    // pause the thread instead of executing its next instruction, until it is funded and resumed.
+    let transaction_fee = thread.transaction_fee(ctx.remaining_accounts)?;
+    let worker_fee = match pool.workers.contains(&worker.key()) {
+        true => thread.fee,
+        false => 0,
+    };
+    if thread.is_below_reserve(transaction_fee.saturating_add(worker_fee))? {
+        thread.pause(PauseReason::LowBalance);
+        thread.realloc_account()?;
+
+        // Reimburse signatory for transaction fees, as far as the thread can afford them.
+        let transaction_fee = transaction_fee.min(thread.available_lamports()?);
+        thread.sub_lamports(transaction_fee)?;
+        signatory.add_lamports(transaction_fee)?;
+        return Ok(());
+    }
+
+    // Deleting the thread closes its account, which leaves nothing to pay the signatory and the worker from afterwards.
+    // Pay them beforehand.
+    let thread_pubkey = thread.key();
+    let closes_thread = is_deletion
+        && thread.next_instruction.as_ref().is_some_and(|ix| {
+            ix.accounts
+                .iter()
+                .any(|account| account.pubkey.eq(&thread_pubkey))
+        });
+    if closes_thread {
+        thread.sub_lamports(transaction_fee.saturating_add(worker_fee))?;
+        signatory.add_lamports(transaction_fee)?;
+        fee.add_lamports(worker_fee)?;
+    }
+
     // Record the worker's lamports before invoking inner ixs.
     let signatory_lamports_pre = signatory.lamports();
 
@@ -102,6 +152,12 @@ pub fn handler(ctx: Context<ThreadExec>) -> Result<()> {
     // Verify the inner instruction did not write data to the signatory address.
     require!(signatory.data_is_empty(), SablierError::UnauthorizedWrite);
 
+    // If the inner instruction closed the thread, there is nothing left to update.
+    // The deletion of the thread always closes it, so it was paid for above.
+    if thread.get_lamports() == 0 {
+        return Ok(());
+    }
+
     // Parse the thread response
     let thread_response: Option<ThreadResponse> = match get_return_data() {
         None => None,
@@ -122,17 +178,22 @@ pub fn handler(ctx: Context<ThreadExec>) -> Result<()> {
         next_instruction = thread_response.dynamic_instruction;
 
         // Update the trigger.
+        // The trigger context is kept, so the primary trigger must keep its variant.
         if let Some(trigger) = thread_response.trigger {
             require!(
-                std::mem::discriminant(&thread.trigger) == std::mem::discriminant(&trigger),
+                std::mem::discriminant(thread.trigger.primary())
+                    == std::mem::discriminant(trigger.primary()),
                 SablierError::InvalidTriggerVariant
             );
+            require!(trigger.is_valid(), SablierError::InvalidTrigger);
             thread.trigger = trigger.clone();
+            thread.extension.compiled_schedule =
+                thread.extension.cron.compile(&trigger, &thread_pubkey)?;
 
             // If the user updates an account trigger, the trigger context is no longer valid.
             // Here we reset the trigger context to zero to re-prime the trigger.
             thread.exec_context = Some(ExecContext {
-                trigger_context: match trigger {
+                trigger_context: match trigger.primary() {
                     Trigger::Account {
                         address: _,
                         offset: _,
@@ -154,21 +215,19 @@ pub fn handler(ctx: Context<ThreadExec>) -> Result<()> {
         }
     }
 
+    // If the thread completed its last execution cycle, close it to its authority.
+    if next_instruction.is_none()
+        && thread
+            .extension
+            .lifetime
+            .is_exhausted(thread.extension.executions)
+    {
+        close_to = close_to.or(Some(thread.authority));
+    }
+
     // Update the next instruction.
     if let Some(close_to) = close_to {
-        thread.next_instruction = Some(
-            Instruction {
-                program_id: crate::ID,
-                accounts: crate::accounts::ThreadDelete {
-                    authority: thread.key(),
-                    close_to,
-                    thread: thread.key(),
-                }
-                .to_account_metas(Some(true)),
-                data: crate::instruction::ThreadDelete {}.data(),
-            }
-            .into(),
-        );
+        thread.next_instruction = Some(thread.delete_instruction(close_to));
     } else {
         thread.next_instruction = next_instruction;
     }
@@ -190,32 +249,78 @@ pub fn handler(ctx: Context<ThreadExec>) -> Result<()> {
     let signatory_lamports_post = signatory.lamports();
     let mut signatory_reimbursement =
         signatory_lamports_pre.saturating_sub(signatory_lamports_post);
+
+    // If the inner instruction spent more than the thread reimburses, only reimburse the cap.
+    let reimbursement_capped = thread
+        .extension
+        .budget
+        .max_reimbursement
+        .is_some_and(|max_reimbursement| signatory_reimbursement > max_reimbursement);
+    if let Some(max_reimbursement) = thread.extension.budget.max_reimbursement {
+        signatory_reimbursement = signatory_reimbursement.min(max_reimbursement);
+    }
     if should_reimburse_transaction {
-        signatory_reimbursement += TRANSACTION_BASE_FEE_REIMBURSEMENT;
+        signatory_reimbursement += transaction_fee;
     }
+
+    // Only pay out what the thread can spend above its reserve, reimbursing the signatory before paying the worker.
+    // Threads about to be deleted may spend their reserve, which would otherwise go to `close_to`.
+    let spendable_lamports = match close_to {
+        Some(_) => thread.available_lamports()?,
+        None => thread.spendable_lamports()?,
+    };
+    let payout_capped = signatory_reimbursement.saturating_add(worker_fee) > spendable_lamports;
+    let signatory_reimbursement = signatory_reimbursement.min(spendable_lamports);
+    let worker_fee = worker_fee.min(spendable_lamports - signatory_reimbursement);
     if signatory_reimbursement > 0 {
         thread.sub_lamports(signatory_reimbursement)?;
         signatory.add_lamports(signatory_reimbursement)?;
     }
 
     // If the worker is in the pool, debit from the thread account and payout to the worker's fee account.
-    if pool.workers.contains(&worker.key()) {
-        thread.sub_lamports(thread.fee)?;
-        fee.add_lamports(thread.fee)?;
+    if worker_fee > 0 {
+        thread.sub_lamports(worker_fee)?;
+        fee.add_lamports(worker_fee)?;
+    }
+
+    // If the thread could not pay out in full or its balance fell below its reserve, exceeded its reimbursement cap
+    // or exhausted its budget, pause it unless it is about to be deleted.
+    let budget_exhausted = thread
+        .extension
+        .record_spending(signatory_reimbursement + worker_fee, clock.unix_timestamp);
+    if close_to.is_none() {
+        if payout_capped || thread.is_below_reserve(0)? {
+            thread.pause(PauseReason::LowBalance);
+        } else if reimbursement_capped {
+            thread.pause(PauseReason::ReimbursementCapExceeded);
+        } else if budget_exhausted {
+            thread.pause(PauseReason::BudgetExhausted);
+        }
     }
 
-    let exec_ctx = &mut thread.exec_context.unwrap();
+    // Update execution context for Cron and Periodic triggers.
+    restart_schedule(thread, &clock);
+
+    // Realloc the thread account
+    thread.realloc_account()?;
 
-    // Update execution context for Cron and Periodic triggers
-    // This ensures the next execution is scheduled based on the actual execution time,
-    // rather than the theoretical scheduled time, preventing drift in long-running threads
-    match thread.trigger {
+    Ok(())
+}
+
+/// Restarts the schedule of threads with skippable cron and periodic triggers from the current moment.
+/// This ensures the next execution is scheduled based on the actual execution time,
+/// rather than the theoretical scheduled time, preventing drift in long-running threads.
+fn restart_schedule(thread: &mut ThreadV2, clock: &Clock) {
+    let Some(exec_ctx) = thread.exec_context.as_mut() else {
+        return;
+    };
+    match thread.trigger.primary() {
         Trigger::Cron { skippable, .. } => {
-            if skippable {
+            if *skippable {
                 exec_ctx.last_exec_at = clock.slot;
-                exec_ctx.trigger_context = TriggerContext::Cron {
-                    started_at: clock.unix_timestamp,
-                };
+                if let TriggerContext::Cron { started_at, .. } = &mut exec_ctx.trigger_context {
+                    *started_at = clock.unix_timestamp;
+                }
             }
         }
         Trigger::Periodic { .. } => {
@@ -226,6 +331,106 @@ pub fn handler(ctx: Context<ThreadExec>) -> Result<()> {
         }
         _ => (),
     }
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use sablier_utils::{account::AccountExtension, thread::TriggerCondition};
+
+    use super::*;
+
+    fn thread(trigger: Trigger, trigger_context: TriggerContext) -> ThreadV2 {
+        ThreadV2 {
+            authority: Pubkey::new_unique(),
+            bump: 255,
+            created_at: ClockData {
+                slot: 0,
+                epoch: 0,
+                unix_timestamp: 0,
+            },
+            domain: None,
+            exec_context: Some(ExecContext {
+                exec_index: 0,
+                execs_since_slot: 1,
+                last_exec_at: 10,
+                trigger_context,
+            }),
+            fee: 0,
+            id: b"drift".to_vec(),
+            instructions: vec![],
+            name: None,
+            next_instruction: None,
+            paused: false,
+            rate_limit: 1,
+            trigger,
+            extension: AccountExtension::default(),
+        }
+    }
+
+    fn clock() -> Clock {
+        Clock {
+            slot: 20,
+            unix_timestamp: 200,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn test_restart_schedule_saves_context() {
+        let mut cron = thread(
+            Trigger::Cron {
+                schedule: "0 * * * * *".into(),
+                skippable: true,
+            },
+            TriggerContext::Cron {
+                started_at: 120,
+                skipped: 3,
+            },
+        );
+        restart_schedule(&mut cron, &clock());
+        let exec_context = cron.exec_context.unwrap();
+        assert_eq!(exec_context.last_exec_at, 20);
+        assert_eq!(
+            exec_context.trigger_context,
+            TriggerContext::Cron {
+                started_at: 200,
+                skipped: 3,
+            }
+        );
+
+        // Periodic triggers wrapped in a compound trigger restart as well.
+        let mut periodic = thread(
+            Trigger::Compound {
+                trigger: Trigger::Periodic { delay: 60 }.into(),
+                conditions: vec![TriggerCondition::NotAfter { unix_ts: 1000 }],
+            },
+            TriggerContext::Periodic { started_at: 120 },
+        );
+        restart_schedule(&mut periodic, &clock());
+        let exec_context = periodic.exec_context.unwrap();
+        assert_eq!(exec_context.last_exec_at, 20);
+        assert_eq!(
+            exec_context.trigger_context,
+            TriggerContext::Periodic { started_at: 200 }
+        );
+    }
+
+    #[test]
+    fn test_restart_schedule_keeps_non_skippable_cron() {
+        let context = TriggerContext::Cron {
+            started_at: 120,
+            skipped: 0,
+        };
+        let mut cron = thread(
+            Trigger::Cron {
+                schedule: "0 * * * * *".into(),
+                skippable: false,
+            },
+            context,
+        );
+        restart_schedule(&mut cron, &clock());
+        let exec_context = cron.exec_context.unwrap();
+        assert_eq!(exec_context.last_exec_at, 10);
+        assert_eq!(exec_context.trigger_context, context);
+    }
 }
@@ -29,7 +29,7 @@ pub struct ThreadInstructionAdd<'info> {
         bump = thread.bump,
         has_one = authority
     )]
-    pub thread: Account<'info, Thread>,
+    pub thread: Account<'info, ThreadV2>,
 }
 
 pub fn handler(
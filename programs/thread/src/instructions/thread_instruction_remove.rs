@@ -22,7 +22,7 @@ pub struct ThreadInstructionRemove<'info> {
         bump = thread.bump,
         has_one = authority
     )]
-    pub thread: Account<'info, Thread>,
+    pub thread: Account<'info, ThreadV2>,
 }
 
 pub fn handler(ctx: Context<ThreadInstructionRemove>, index: u64) -> Result<()> {
@@ -32,5 +32,8 @@ pub fn handler(ctx: Context<ThreadInstructionRemove>, index: u64) -> Result<()>
     // Pause the thread
     thread.instructions.remove(index as usize);
 
+    // Realloc the thread account
+    thread.realloc_account()?;
+
     Ok(())
 }
@@ -1,16 +1,18 @@
 use std::{
     collections::hash_map::DefaultHasher,
     hash::{Hash, Hasher},
-    str::FromStr,
 };
 
 use anchor_lang::prelude::*;
 use chrono::DateTime;
-use sablier_cron::Schedule;
+use sablier_cron::Calendar;
 use sablier_network_program::state::{Worker, WorkerAccount};
 use sablier_utils::{
-    pyth::{self, PriceUpdateV2},
-    thread::Trigger,
+    hash::account_data_hash,
+    oracle::OraclePrice,
+    pyth::{self, get_oracle_key, PriceUpdateV2},
+    thread::{PriceType, Trigger, TriggerCondition},
+    token::read_token_amount,
 };
 
 use crate::{constants::*, errors::*, state::*};
@@ -35,7 +37,7 @@ pub struct ThreadKickoff<'info> {
         constraint = !thread.paused @ SablierError::ThreadPaused,
         constraint = thread.next_instruction.is_none() @ SablierError::ThreadBusy,
     )]
-    pub thread: Account<'info, Thread>,
+    pub thread: Account<'info, ThreadV2>,
 
     /// The worker.
     #[account(address = worker.pubkey())]
@@ -48,14 +50,104 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
     let thread = &mut ctx.accounts.thread;
     let clock = Clock::get()?;
 
-    match &thread.trigger {
+    // Once the thread has expired, refuse to kick it off.
+    // Instead, queue the deletion of the thread, which is executed by the next exec.
+    if thread.is_expired(clock.unix_timestamp) {
+        // Threads which never ran have no exec context, yet one is required to exec the deletion.
+        thread.exec_context.get_or_insert(ExecContext {
+            exec_index: 0,
+            execs_since_slot: 0,
+            last_exec_at: clock.slot,
+            trigger_context: TriggerContext::Now,
+        });
+        let authority = thread.authority;
+        thread.next_instruction = Some(thread.delete_instruction(authority));
+        thread.realloc_account()?;
+
+        // Reimburse signatory for transaction fees.
+        let transaction_fee = thread.transaction_fee(ctx.remaining_accounts)?;
+        thread.sub_lamports(transaction_fee)?;
+        signatory.add_lamports(transaction_fee)?;
+        return Ok(());
+    }
+
+    // If paying for the kickoff would bring the balance of the thread below its reserve,
+    // pause the thread instead of kicking it off, until it is funded and resumed.
+    let transaction_fee = thread.transaction_fee(ctx.remaining_accounts)?;
+    if thread.is_below_reserve(transaction_fee)? {
+        thread.pause(PauseReason::LowBalance);
+        thread.realloc_account()?;
+
+        // Reimburse signatory for transaction fees, as far as the thread can afford them.
+        let transaction_fee = transaction_fee.min(thread.available_lamports()?);
+        thread.sub_lamports(transaction_fee)?;
+        signatory.add_lamports(transaction_fee)?;
+        return Ok(());
+    }
+    require!(
+        !thread
+            .extension
+            .lifetime
+            .is_before_window(clock.unix_timestamp),
+        SablierError::ThreadNotStarted
+    );
+
+    // Verify the trigger of the thread is active.
+    let thread_pubkey = thread.key();
+    let kickoff = activate_trigger(thread, &thread_pubkey, ctx.remaining_accounts, &clock)?;
+
+    // If the trigger is active, count the execution and queue the first instruction.
+    if kickoff == Kickoff::Activated {
+        thread.extension.executions = thread.extension.executions.saturating_add(1);
+        if let Some(kickoff_instruction) = thread.instructions.first() {
+            thread.next_instruction = Some(kickoff_instruction.clone());
+        }
+    }
+
+    // If the thread exhausted its budget, pause it before its instructions are executed.
+    let transaction_fee = thread.transaction_fee(ctx.remaining_accounts)?;
+    if thread
+        .extension
+        .record_spending(transaction_fee, clock.unix_timestamp)
+    {
+        thread.pause(PauseReason::BudgetExhausted);
+    }
+
+    // Realloc the thread account
+    thread.realloc_account()?;
+
+    // Reimburse signatory for transaction fees.
+    thread.sub_lamports(transaction_fee)?;
+    signatory.add_lamports(transaction_fee)?;
+
+    Ok(())
+}
+
+/// The outcome of a kickoff.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kickoff {
+    /// The trigger is active: the thread starts an execution cycle.
+    Activated,
+    /// The trigger context was updated without starting an execution cycle.
+    Skipped,
+}
+
+/// Verifies the trigger of a thread is active and updates its trigger context.
+/// Compound triggers are kicked off according to their primary trigger, then their conditions are verified.
+fn activate_trigger(
+    thread: &mut ThreadV2,
+    thread_pubkey: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+    clock: &Clock,
+) -> Result<Kickoff> {
+    match thread.trigger.primary() {
         Trigger::Account {
             address,
             offset,
             size,
         } => {
             // Verify proof that account data has been updated.
-            match ctx.remaining_accounts.first() {
+            match remaining_accounts.first() {
                 None => {
                     return Err(SablierError::TriggerConditionFailed.into());
                 }
@@ -66,17 +158,9 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
                         SablierError::TriggerConditionFailed
                     );
 
-                    // Begin computing the data hash of this account.
-                    let mut hasher = DefaultHasher::new();
+                    // Compute the data hash of the monitored byte range.
                     let data = &account_info.try_borrow_data()?;
-                    let offset = *offset as usize;
-                    let range_end = offset + *size as usize;
-                    if data.len().gt(&range_end) {
-                        data[offset..range_end].hash(&mut hasher);
-                    } else {
-                        data[offset..].hash(&mut hasher)
-                    }
-                    let data_hash = hasher.finish();
+                    let data_hash = account_data_hash(data, *offset, *size);
 
                     // Verify the data hash is different than the prior data hash.
                     if let Some(exec_context) = thread.exec_context {
@@ -84,6 +168,19 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
                             TriggerContext::Account {
                                 data_hash: prior_data_hash,
                             } => {
+                                // Threads primed before the switch to a stable hash hold a legacy hash.
+                                // If the data is unchanged, migrate the stored hash without kicking off the thread.
+                                // TODO Remove this branch once the account threads primed before the switch have been kicked off.
+                                if legacy_data_hash(data, *offset, *size) == Some(prior_data_hash) {
+                                    msg!("Migrating legacy data hash: {}", prior_data_hash);
+                                    thread.exec_context = Some(ExecContext {
+                                        trigger_context: TriggerContext::Account { data_hash },
+                                        ..exec_context
+                                    });
+
+                                    return Ok(Kickoff::Skipped);
+                                }
+
                                 require!(
                                     data_hash.ne(&prior_data_hash),
                                     SablierError::TriggerConditionFailed
@@ -96,7 +193,6 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
                     // Set a new exec context with the new data hash and slot number.
                     thread.exec_context = Some(ExecContext {
                         exec_index: 0,
-                        execs_since_reimbursement: 0,
                         execs_since_slot: 0,
                         last_exec_at: clock.slot,
                         trigger_context: TriggerContext::Account { data_hash },
@@ -112,13 +208,21 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
             let reference_timestamp = match thread.exec_context {
                 None => thread.created_at.unix_timestamp,
                 Some(exec_context) => match exec_context.trigger_context {
-                    TriggerContext::Cron { started_at } => started_at,
+                    TriggerContext::Cron { started_at, .. } => started_at,
                     _ => return Err(SablierError::InvalidThreadState.into()),
                 },
             };
 
             // Verify the current timestamp is greater than or equal to the threshold timestamp.
-            let threshold_timestamp = next_timestamp(reference_timestamp, schedule)
+            #[cfg(feature = "compute-units")]
+            log_evaluation_compute_units(
+                &thread.extension,
+                schedule,
+                thread_pubkey,
+                reference_timestamp,
+            );
+            let schedule = thread.extension.cron_calendar(schedule, thread_pubkey)?;
+            let threshold_timestamp = next_timestamp(reference_timestamp, &schedule)
                 .ok_or(SablierError::TriggerConditionFailed)?;
             msg!(
                 "Threshold timestamp: {}, clock timestamp: {}",
@@ -131,9 +235,24 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
             );
 
             // If the schedule is marked as skippable, set the started_at of the exec context to be the current timestamp.
-            // Otherwise, the exec context must iterate through each scheduled kickoff moment.
+            // Otherwise, the exec context must iterate through each scheduled kickoff moment,
+            // unless more moments were missed than the thread may catch up on: then they are coalesced into this kickoff.
+            let mut skipped = 0;
             let started_at = if *skippable {
                 clock.unix_timestamp
+            } else if let Some(max_catch_up) = thread.extension.cron.max_catch_up {
+                let missed_moments = count_moments(
+                    threshold_timestamp,
+                    clock.unix_timestamp,
+                    &schedule,
+                    CRON_CATCH_UP_SCAN_LIMIT,
+                );
+                if missed_moments > max_catch_up {
+                    skipped = missed_moments;
+                    clock.unix_timestamp
+                } else {
+                    threshold_timestamp
+                }
             } else {
                 threshold_timestamp
             };
@@ -141,10 +260,12 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
             // Set the exec context.
             thread.exec_context = Some(ExecContext {
                 exec_index: 0,
-                execs_since_reimbursement: 0,
                 execs_since_slot: 0,
                 last_exec_at: clock.slot,
-                trigger_context: TriggerContext::Cron { started_at },
+                trigger_context: TriggerContext::Cron {
+                    started_at,
+                    skipped,
+                },
             });
         }
         Trigger::Now => {
@@ -155,7 +276,6 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
             );
             thread.exec_context = Some(ExecContext {
                 exec_index: 0,
-                execs_since_reimbursement: 0,
                 execs_since_slot: 0,
                 last_exec_at: clock.slot,
                 trigger_context: TriggerContext::Now,
@@ -165,7 +285,6 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
             require!(clock.slot.ge(slot), SablierError::TriggerConditionFailed);
             thread.exec_context = Some(ExecContext {
                 exec_index: 0,
-                execs_since_reimbursement: 0,
                 execs_since_slot: 0,
                 last_exec_at: clock.slot,
                 trigger_context: TriggerContext::Slot { started_at: *slot },
@@ -175,7 +294,6 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
             require!(clock.epoch.ge(epoch), SablierError::TriggerConditionFailed);
             thread.exec_context = Some(ExecContext {
                 exec_index: 0,
-                execs_since_reimbursement: 0,
                 execs_since_slot: 0,
                 last_exec_at: clock.slot,
                 trigger_context: TriggerContext::Epoch { started_at: *epoch },
@@ -188,7 +306,6 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
             );
             thread.exec_context = Some(ExecContext {
                 exec_index: 0,
-                execs_since_reimbursement: 0,
                 execs_since_slot: 0,
                 last_exec_at: clock.slot,
                 trigger_context: TriggerContext::Timestamp {
@@ -202,58 +319,61 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
             limit,
         } => {
             // Verify price limit has been reached.
-            match ctx.remaining_accounts.first() {
+            match remaining_accounts.first() {
                 None => {
                     return Err(SablierError::TriggerConditionFailed.into());
                 }
                 Some(account_info) => {
+                    // The deprecated trigger reads the spot price of shard 0, compared like a `PythPrice` trigger
+                    // with a limit in whole units.
+                    require_keys_eq!(
+                        *account_info.key,
+                        get_oracle_key(0, *feed_id),
+                        SablierError::TriggerConditionFailed
+                    );
                     require_keys_eq!(
                         *account_info.owner,
                         pyth::ID,
                         SablierError::TriggerConditionFailed
                     );
-                    const STALENESS_THRESHOLD: u64 = 60; // staleness threshold in seconds
                     let price_update =
                         PriceUpdateV2::try_deserialize(&mut account_info.data.borrow().as_ref())?;
 
+                    // Verify the price update is for this feed and fresh enough.
                     let current_price = price_update.get_price_no_older_than(
-                        &Clock::get()?,
-                        STALENESS_THRESHOLD,
+                        clock,
+                        PYTH_STALENESS_THRESHOLD,
                         feed_id,
                     )?;
 
-                    match equality {
-                        Equality::GreaterThanOrEqual => {
-                            require!(
-                                current_price.price.ge(limit),
-                                SablierError::TriggerConditionFailed
-                            );
-                            thread.exec_context = Some(ExecContext {
-                                exec_index: 0,
-                                execs_since_reimbursement: 0,
-                                execs_since_slot: 0,
-                                last_exec_at: clock.slot,
-                                trigger_context: TriggerContext::Pyth {
-                                    price: current_price.price,
-                                },
-                            });
-                        }
-                        Equality::LessThanOrEqual => {
-                            require!(
-                                current_price.price.le(limit),
-                                SablierError::TriggerConditionFailed
-                            );
-                            thread.exec_context = Some(ExecContext {
-                                exec_index: 0,
-                                execs_since_reimbursement: 0,
-                                execs_since_slot: 0,
-                                last_exec_at: clock.slot,
-                                trigger_context: TriggerContext::Pyth {
-                                    price: current_price.price,
-                                },
-                            });
-                        }
-                    }
+                    // Get the price recorded at the last kickoff.
+                    let prior_price = match thread.exec_context {
+                        None => None,
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::Pyth { price } => Some(price),
+                            _ => return Err(SablierError::InvalidThreadState.into()),
+                        },
+                    };
+
+                    require!(
+                        price_update.price_message.meets_limit(
+                            PriceType::Spot,
+                            None,
+                            equality,
+                            *limit,
+                            0,
+                            prior_price
+                        ),
+                        SablierError::TriggerConditionFailed
+                    );
+                    thread.exec_context = Some(ExecContext {
+                        exec_index: 0,
+                        execs_since_slot: 0,
+                        last_exec_at: clock.slot,
+                        trigger_context: TriggerContext::Pyth {
+                            price: current_price.price,
+                        },
+                    });
                 }
             }
         }
@@ -283,7 +403,6 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
             // Set the exec context.
             thread.exec_context = Some(ExecContext {
                 exec_index: 0,
-                execs_since_reimbursement: 0,
                 execs_since_slot: 0,
                 last_exec_at: clock.slot,
                 trigger_context: TriggerContext::Periodic {
@@ -291,27 +410,776 @@ pub fn handler(ctx: Context<ThreadKickoff>) -> Result<()> {
                 },
             });
         }
+        Trigger::AccountValue {
+            address,
+            offset,
+            value_type,
+            endianness,
+            equality,
+            threshold,
+        } => {
+            // Verify the value condition is met.
+            match remaining_accounts.first() {
+                None => {
+                    return Err(SablierError::TriggerConditionFailed.into());
+                }
+                Some(account_info) => {
+                    // Verify the remaining account is the account this thread is listening for.
+                    require!(
+                        address.eq(account_info.key),
+                        SablierError::TriggerConditionFailed
+                    );
+
+                    // Read the value from the account data.
+                    let value = value_type
+                        .read(&account_info.try_borrow_data()?, *offset, *endianness)
+                        .ok_or(SablierError::TriggerConditionFailed)?;
+
+                    // Get the value recorded at the last kickoff.
+                    let prior_value = match thread.exec_context {
+                        None => None,
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::AccountValue { value } => Some(value),
+                            _ => return Err(SablierError::InvalidThreadState.into()),
+                        },
+                    };
+
+                    // The trigger fires when the value crosses the threshold.
+                    // Once the value no longer meets the threshold, it is recorded to re-arm the trigger.
+                    let is_released = equality.is_released(value, *threshold, prior_value);
+                    require!(
+                        is_released || equality.is_crossed(value, *threshold, prior_value),
+                        SablierError::TriggerConditionFailed
+                    );
+                    thread.exec_context = Some(ExecContext {
+                        exec_index: 0,
+                        execs_since_slot: 0,
+                        last_exec_at: clock.slot,
+                        trigger_context: TriggerContext::AccountValue { value },
+                    });
+                    if is_released {
+                        msg!("Trigger released, re-arming it");
+                        return Ok(Kickoff::Skipped);
+                    }
+                }
+            }
+        }
+        Trigger::TokenBalance {
+            address,
+            equality,
+            threshold,
+        } => {
+            // Verify the balance condition is met.
+            match remaining_accounts.first() {
+                None => {
+                    return Err(SablierError::TriggerConditionFailed.into());
+                }
+                Some(account_info) => {
+                    // Verify the remaining account is the token account this thread is listening for.
+                    require!(
+                        address.eq(account_info.key),
+                        SablierError::TriggerConditionFailed
+                    );
+
+                    // Read the balance of the token account.
+                    let amount =
+                        read_token_amount(account_info.owner, &account_info.try_borrow_data()?)
+                            .ok_or(SablierError::TriggerConditionFailed)?;
+
+                    // Get the balance recorded at the last kickoff.
+                    let prior_amount = match thread.exec_context {
+                        None => None,
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::TokenBalance { amount } => Some(amount as i128),
+                            _ => return Err(SablierError::InvalidThreadState.into()),
+                        },
+                    };
+
+                    // The trigger fires when the balance crosses the threshold.
+                    // Once the balance no longer meets the threshold, it is recorded to re-arm the trigger.
+                    let (balance, threshold) = (amount as i128, *threshold as i128);
+                    let is_released = equality.is_released(balance, threshold, prior_amount);
+                    require!(
+                        is_released || equality.is_crossed(balance, threshold, prior_amount),
+                        SablierError::TriggerConditionFailed
+                    );
+                    thread.exec_context = Some(ExecContext {
+                        exec_index: 0,
+                        execs_since_slot: 0,
+                        last_exec_at: clock.slot,
+                        trigger_context: TriggerContext::TokenBalance { amount },
+                    });
+                    if is_released {
+                        msg!("Trigger released, re-arming it");
+                        return Ok(Kickoff::Skipped);
+                    }
+                }
+            }
+        }
+        Trigger::PythPrice {
+            feed_id,
+            shard_id,
+            price_type,
+            equality,
+            limit,
+            limit_exponent,
+            max_age,
+            max_confidence_bps,
+        } => {
+            // Verify price limit has been reached.
+            match remaining_accounts.first() {
+                None => {
+                    return Err(SablierError::TriggerConditionFailed.into());
+                }
+                Some(account_info) => {
+                    // Verify the remaining account is the price feed account of the thread's shard.
+                    require_keys_eq!(
+                        *account_info.key,
+                        get_oracle_key(*shard_id, *feed_id),
+                        SablierError::TriggerConditionFailed
+                    );
+                    require_keys_eq!(
+                        *account_info.owner,
+                        pyth::ID,
+                        SablierError::TriggerConditionFailed
+                    );
+                    let price_update =
+                        PriceUpdateV2::try_deserialize(&mut account_info.data.borrow().as_ref())?;
+
+                    // Verify the price update is for this feed and fresh enough.
+                    price_update.get_price_no_older_than(clock, *max_age, feed_id)?;
+                    let price_message = &price_update.price_message;
+
+                    // Get the price recorded at the last kickoff.
+                    let prior_price = match thread.exec_context {
+                        None => None,
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::Pyth { price } => Some(price),
+                            _ => return Err(SablierError::InvalidThreadState.into()),
+                        },
+                    };
+
+                    require!(
+                        price_message.meets_limit(
+                            *price_type,
+                            *max_confidence_bps,
+                            equality,
+                            *limit,
+                            *limit_exponent,
+                            prior_price
+                        ),
+                        SablierError::TriggerConditionFailed
+                    );
+                    thread.exec_context = Some(ExecContext {
+                        exec_index: 0,
+                        execs_since_slot: 0,
+                        last_exec_at: clock.slot,
+                        trigger_context: TriggerContext::Pyth {
+                            price: price_message.price_of(*price_type).0,
+                        },
+                    });
+                }
+            }
+        }
+        Trigger::Oracle {
+            provider,
+            feed,
+            equality,
+            limit,
+            limit_exponent,
+            max_age,
+        } => {
+            // Verify price limit has been reached.
+            match remaining_accounts.first() {
+                None => {
+                    return Err(SablierError::TriggerConditionFailed.into());
+                }
+                Some(account_info) => {
+                    // Verify the remaining account is the feed this thread is listening for.
+                    require!(
+                        feed.eq(account_info.key),
+                        SablierError::TriggerConditionFailed
+                    );
+
+                    // Read the price and verify it is fresh enough.
+                    let price = OraclePrice::try_from_account(
+                        *provider,
+                        account_info.owner,
+                        &account_info.try_borrow_data()?,
+                    )
+                    .ok_or(SablierError::TriggerConditionFailed)?;
+                    require!(
+                        price.is_fresh(clock.unix_timestamp, *max_age),
+                        SablierError::TriggerConditionFailed
+                    );
+
+                    // Get the price recorded at the last kickoff.
+                    let prior_price = match thread.exec_context {
+                        None => None,
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::Oracle { price } => Some(price),
+                            _ => return Err(SablierError::InvalidThreadState.into()),
+                        },
+                    };
+
+                    require!(
+                        price.meets_limit(equality, *limit, *limit_exponent, prior_price),
+                        SablierError::TriggerConditionFailed
+                    );
+                    thread.exec_context = Some(ExecContext {
+                        exec_index: 0,
+                        execs_since_slot: 0,
+                        last_exec_at: clock.slot,
+                        trigger_context: TriggerContext::Oracle { price: price.price },
+                    });
+                }
+            }
+        }
+        Trigger::Chain {
+            upstream,
+            require_last_instruction,
+        } => {
+            // Verify the upstream thread completed an execution cycle.
+            match remaining_accounts.first() {
+                None => {
+                    return Err(SablierError::TriggerConditionFailed.into());
+                }
+                Some(account_info) => {
+                    // Verify the remaining account is the upstream thread this thread is listening for.
+                    require!(
+                        upstream.eq(account_info.key),
+                        SablierError::TriggerConditionFailed
+                    );
+                    require_keys_eq!(
+                        *account_info.owner,
+                        crate::ID,
+                        SablierError::TriggerConditionFailed
+                    );
+                    let upstream_thread = VersionedThread::try_deserialize(
+                        &mut account_info.try_borrow_data()?.as_ref(),
+                    )?;
+                    let completed_at = upstream_thread
+                        .completed_cycle_at(*require_last_instruction)
+                        .ok_or(SablierError::TriggerConditionFailed)?;
+
+                    // Verify the cycle completed after the last kickoff, or after this thread was created.
+                    let reference_slot = match thread.exec_context {
+                        None => thread.created_at.slot,
+                        Some(exec_context) => match exec_context.trigger_context {
+                            TriggerContext::Chain { completed_at } => completed_at,
+                            _ => return Err(SablierError::InvalidThreadState.into()),
+                        },
+                    };
+                    require!(
+                        completed_at.gt(&reference_slot),
+                        SablierError::TriggerConditionFailed
+                    );
+
+                    thread.exec_context = Some(ExecContext {
+                        exec_index: 0,
+                        execs_since_slot: 0,
+                        last_exec_at: clock.slot,
+                        trigger_context: TriggerContext::Chain { completed_at },
+                    });
+                }
+            }
+        }
+        Trigger::Signal { .. } => {
+            // Verify the thread received a signal since its last kickoff.
+            let Some(exec_context) = thread.exec_context else {
+                return Err(SablierError::TriggerConditionFailed.into());
+            };
+            let TriggerContext::Signal {
+                signals,
+                handled,
+                payload,
+            } = exec_context.trigger_context
+            else {
+                return Err(SablierError::InvalidThreadState.into());
+            };
+            require!(signals.gt(&handled), SablierError::TriggerConditionFailed);
+
+            // Mark all the received signals as handled.
+            thread.exec_context = Some(ExecContext {
+                exec_index: 0,
+                execs_since_slot: 0,
+                last_exec_at: clock.slot,
+                trigger_context: TriggerContext::Signal {
+                    signals,
+                    handled: signals,
+                    payload,
+                },
+            });
+        }
+        Trigger::SlotInterval {
+            interval,
+            start_slot,
+            skippable,
+        } => {
+            // Get the threshold slot of the next kickoff.
+            let threshold_slot = match thread.exec_context {
+                None => start_slot.unwrap_or(thread.created_at.slot.saturating_add(*interval)),
+                Some(exec_context) => match exec_context.trigger_context {
+                    TriggerContext::SlotInterval { started_at } => {
+                        started_at.saturating_add(*interval)
+                    }
+                    _ => return Err(SablierError::InvalidThreadState.into()),
+                },
+            };
+
+            // Verify the current slot is greater than or equal to the threshold slot.
+            msg!(
+                "Threshold slot: {}, clock slot: {}",
+                threshold_slot,
+                clock.slot
+            );
+            require!(
+                clock.slot.ge(&threshold_slot),
+                SablierError::TriggerConditionFailed
+            );
+
+            // If the schedule is marked as skippable, set the started_at of the exec context to be the current slot.
+            // Otherwise, the exec context must iterate through each scheduled kickoff slot.
+            let started_at = if *skippable {
+                clock.slot
+            } else {
+                threshold_slot
+            };
+
+            // Set the exec context.
+            thread.exec_context = Some(ExecContext {
+                exec_index: 0,
+                execs_since_slot: 0,
+                last_exec_at: clock.slot,
+                trigger_context: TriggerContext::SlotInterval { started_at },
+            });
+        }
+        Trigger::Compound { .. } => return Err(SablierError::InvalidTrigger.into()),
     }
 
-    // If we make it here, the trigger is active. Update the next instruction and be done.
-    if let Some(kickoff_instruction) = thread.instructions.first() {
-        thread.next_instruction = Some(kickoff_instruction.clone());
+    // Verify the conditions of compound triggers hold.
+    // If they don't at a moment of a recurring schedule, the moment is skipped without executing the thread,
+    // rather than left due until the conditions hold.
+    if !conditions_hold(thread.trigger.conditions(), remaining_accounts, clock)? {
+        require!(
+            is_recurring(thread.trigger.primary()),
+            SablierError::TriggerConditionFailed
+        );
+        msg!("Trigger conditions not met, skipping the moment");
+        return Ok(Kickoff::Skipped);
     }
 
-    // Realloc the thread account
-    thread.realloc_account()?;
+    Ok(Kickoff::Activated)
+}
 
-    // Reimburse signatory for transaction fee.
-    thread.sub_lamports(TRANSACTION_BASE_FEE_REIMBURSEMENT)?;
-    signatory.add_lamports(TRANSACTION_BASE_FEE_REIMBURSEMENT)?;
+/// Returns true if the trigger fires at the moments of a recurring schedule.
+fn is_recurring(trigger: &Trigger) -> bool {
+    matches!(
+        trigger,
+        Trigger::Cron { .. } | Trigger::Periodic { .. } | Trigger::SlotInterval { .. }
+    )
+}
 
-    Ok(())
+/// Returns true if all the conditions of a compound trigger hold in the current state.
+/// Accounts read by the conditions are looked up by address among the remaining accounts.
+fn conditions_hold(
+    conditions: &[TriggerCondition],
+    remaining_accounts: &[AccountInfo],
+    clock: &Clock,
+) -> Result<bool> {
+    for condition in conditions {
+        if !condition_holds(condition, remaining_accounts, clock)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
 }
 
-fn next_timestamp(after: i64, schedule: &str) -> Option<i64> {
-    Schedule::from_str(schedule)
-        .unwrap()
-        .next_after(&DateTime::from_timestamp(after, 0).unwrap())
-        .take()
+/// Returns true if a condition of a compound trigger holds in the current state.
+fn condition_holds(
+    condition: &TriggerCondition,
+    remaining_accounts: &[AccountInfo],
+    clock: &Clock,
+) -> Result<bool> {
+    let find_account = |address: &Pubkey| {
+        remaining_accounts
+            .iter()
+            .find(|account_info| account_info.key.eq(address))
+            .ok_or(SablierError::TriggerConditionFailed)
+    };
+
+    let is_met = match condition {
+        TriggerCondition::NotBefore { unix_ts } => clock.unix_timestamp.ge(unix_ts),
+        TriggerCondition::NotAfter { unix_ts } => clock.unix_timestamp.le(unix_ts),
+        TriggerCondition::Pyth {
+            feed_id,
+            shard_id,
+            price_type,
+            equality,
+            limit,
+            limit_exponent,
+            max_age,
+            max_confidence_bps,
+        } => {
+            let account_info = find_account(&get_oracle_key(*shard_id, *feed_id))?;
+            require_keys_eq!(
+                *account_info.owner,
+                pyth::ID,
+                SablierError::TriggerConditionFailed
+            );
+            let price_update =
+                PriceUpdateV2::try_deserialize(&mut account_info.data.borrow().as_ref())?;
+
+            // Verify the price update is for this feed and fresh enough.
+            price_update.get_price_no_older_than(clock, *max_age, feed_id)?;
+            price_update.price_message.meets_limit(
+                *price_type,
+                *max_confidence_bps,
+                equality,
+                *limit,
+                *limit_exponent,
+                None,
+            )
+        }
+        TriggerCondition::AccountValue {
+            address,
+            offset,
+            value_type,
+            endianness,
+            equality,
+            threshold,
+        } => {
+            let account_info = find_account(address)?;
+            let value = value_type
+                .read(&account_info.try_borrow_data()?, *offset, *endianness)
+                .ok_or(SablierError::TriggerConditionFailed)?;
+            equality.is_met(value, *threshold, None)
+        }
+        TriggerCondition::TokenBalance {
+            address,
+            equality,
+            threshold,
+        } => {
+            let account_info = find_account(address)?;
+            let amount = read_token_amount(account_info.owner, &account_info.try_borrow_data()?)
+                .ok_or(SablierError::TriggerConditionFailed)?;
+            equality.is_met(amount as i128, *threshold as i128, None)
+        }
+    };
+
+    Ok(is_met)
+}
+
+/// Computes the account data hash the way it was computed before `account_data_hash` was introduced.
+/// `DefaultHasher` is not stable across Rust releases, it is only kept to migrate existing trigger contexts.
+/// Legacy hashes only match if the program is built with the same std hasher implementation as before the switch:
+/// SipHash-1-3 keyed with zeros, fed with the length prefix and the bytes of the slice. This holds for the Rust 1.76
+/// toolchain the workspace is pinned to in `rust-toolchain.toml`, and `test_legacy_data_hash` checks it on upgrades.
+fn legacy_data_hash(data: &[u8], offset: u64, size: u64) -> Option<u64> {
+    let offset = offset as usize;
+    let range_end = offset.saturating_add(size as usize);
+    let slice = if data.len().gt(&range_end) {
+        data.get(offset..range_end)
+    } else {
+        data.get(offset..)
+    }?;
+    let mut hasher = DefaultHasher::new();
+    slice.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Logs the compute units consumed by finding the next moment of a schedule from its expression, as kickoffs used to,
+/// and from its compiled form.
+#[cfg(feature = "compute-units")]
+fn log_evaluation_compute_units(
+    extension: &ThreadExtension,
+    schedule: &str,
+    thread_pubkey: &Pubkey,
+    after: i64,
+) {
+    use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+    use sablier_cron::Schedule;
+
+    let Some(after) = DateTime::from_timestamp(after, 0) else {
+        return;
+    };
+    let remaining = sol_remaining_compute_units();
+    let expected = Schedule::parse_seeded(schedule, thread_pubkey.as_ref())
+        .ok()
+        .and_then(|schedule| schedule.next_after(&after));
+    let expression_units = remaining.saturating_sub(sol_remaining_compute_units());
+    let remaining = sol_remaining_compute_units();
+    let actual = extension
+        .cron_schedule(schedule, thread_pubkey)
+        .ok()
+        .and_then(|schedule| schedule.next_after(&after));
+    let compiled_units = remaining.saturating_sub(sol_remaining_compute_units());
+    msg!(
+        "Evaluating the expression consumed {} compute units, the compiled schedule {}",
+        expression_units,
+        compiled_units
+    );
+    msg!("Next moments: {:?}, {:?}", expected, actual);
+}
+
+/// Counts the moments of a schedule after `after` and up to `until`, stopping at `limit`.
+fn count_moments(after: i64, until: i64, schedule: &Calendar, limit: u64) -> u64 {
+    let Some(after) = DateTime::from_timestamp(after, 0) else {
+        return 0;
+    };
+    schedule
+        .after(&after)
+        .take_while(|datetime| datetime.timestamp() <= until)
+        .take(limit as usize)
+        .count() as u64
+}
+
+fn next_timestamp(after: i64, schedule: &Calendar) -> Option<i64> {
+    schedule
+        .next_after(&DateTime::from_timestamp(after, 0)?)
         .map(|datetime| datetime.timestamp())
 }
+
+#[cfg(test)]
+mod tests {
+    use sablier_utils::{
+        account::AccountExtension,
+        pyth::{PriceFeedMessage, VerificationLevel},
+        thread::{Endianness, Equality, ValueType},
+    };
+
+    use super::*;
+
+    /// A thread kicked off every minute while the value of `condition` is at least 10.
+    fn guarded_thread(condition: Pubkey) -> ThreadV2 {
+        ThreadV2 {
+            authority: Pubkey::new_unique(),
+            bump: 255,
+            created_at: ClockData {
+                slot: 0,
+                epoch: 0,
+                unix_timestamp: 0,
+            },
+            domain: None,
+            exec_context: None,
+            fee: 0,
+            id: b"guarded".to_vec(),
+            instructions: vec![],
+            name: None,
+            next_instruction: None,
+            paused: false,
+            rate_limit: 1,
+            trigger: Trigger::Compound {
+                trigger: Trigger::Cron {
+                    schedule: "0 * * * * *".into(),
+                    skippable: false,
+                }
+                .into(),
+                conditions: vec![TriggerCondition::AccountValue {
+                    address: condition,
+                    offset: 0,
+                    value_type: ValueType::U64,
+                    endianness: Endianness::Little,
+                    equality: Equality::GreaterThanOrEqual,
+                    threshold: 10,
+                }],
+            },
+            extension: AccountExtension::default(),
+        }
+    }
+
+    fn kickoff(thread: &mut ThreadV2, condition: &AccountInfo, unix_timestamp: i64) -> Kickoff {
+        let clock = Clock {
+            unix_timestamp,
+            ..Clock::default()
+        };
+        let thread_pubkey = Pubkey::new_unique();
+        activate_trigger(thread, &thread_pubkey, &[condition.clone()], &clock).unwrap()
+    }
+
+    fn cron_started_at(thread: &ThreadV2) -> Option<i64> {
+        match thread.exec_context?.trigger_context {
+            TriggerContext::Cron { started_at, .. } => Some(started_at),
+            _ => None,
+        }
+    }
+
+    /// A price update account of 123.45 for `feed_id`, published at 60.
+    fn price_update_data(feed_id: [u8; 32]) -> Vec<u8> {
+        let price_update = PriceUpdateV2 {
+            write_authority: Pubkey::new_unique(),
+            verification_level: VerificationLevel::Full,
+            price_message: PriceFeedMessage {
+                feed_id,
+                price: 12_345,
+                conf: 10,
+                exponent: -2,
+                publish_time: 60,
+                prev_publish_time: 59,
+                ema_price: 12_000,
+                ema_conf: 10,
+            },
+            posted_slot: 0,
+        };
+        let mut data = [34, 241, 35, 99, 157, 126, 244, 205].to_vec();
+        data.extend(price_update.try_to_vec().unwrap());
+        data
+    }
+
+    #[test]
+    fn test_legacy_data_hash() {
+        // SipHash-1-3 keyed with zeros of the length prefix and the bytes, as hashed before the switch.
+        assert_eq!(legacy_data_hash(b"sablier", 0, 7), Some(30586751283150711));
+    }
+
+    #[test]
+    fn test_compound_guard_skips_moment() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = 5u64.to_le_bytes();
+        let condition = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let mut thread = guarded_thread(key);
+
+        // The guard is false at the first moment: the moment is consumed without starting a cycle.
+        assert_eq!(kickoff(&mut thread, &condition, 60), Kickoff::Skipped);
+        assert_eq!(cron_started_at(&thread), Some(60));
+
+        // The next moment is not due yet.
+        assert!(activate_trigger(
+            &mut thread,
+            &Pubkey::new_unique(),
+            &[condition.clone()],
+            &Clock {
+                unix_timestamp: 61,
+                ..Clock::default()
+            }
+        )
+        .is_err());
+
+        // The guard is true at the next moment.
+        condition
+            .try_borrow_mut_data()
+            .unwrap()
+            .copy_from_slice(&20u64.to_le_bytes());
+        assert_eq!(kickoff(&mut thread, &condition, 120), Kickoff::Activated);
+        assert_eq!(cron_started_at(&thread), Some(120));
+    }
+
+    #[test]
+    fn test_compound_guard_fails_one_time_trigger() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = 5u64.to_le_bytes();
+        let condition = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let mut thread = guarded_thread(key);
+        if let Trigger::Compound { trigger, .. } = &mut thread.trigger {
+            *trigger = Trigger::Timestamp { unix_ts: 60 }.into();
+        }
+
+        // One-time triggers stay due until the guard is true.
+        let clock = Clock {
+            unix_timestamp: 60,
+            ..Clock::default()
+        };
+        assert!(activate_trigger(&mut thread, &key, &[condition.clone()], &clock).is_err());
+    }
+
+    #[test]
+    fn test_pyth_condition_normalizes_limit() {
+        let feed_id = [1; 32];
+        let key = get_oracle_key(0, feed_id);
+        let mut lamports = 0;
+        let mut data = price_update_data(feed_id);
+        let account = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &pyth::ID,
+            false,
+            0,
+        );
+        let clock = Clock {
+            unix_timestamp: 60,
+            ..Clock::default()
+        };
+        let condition = |limit| TriggerCondition::Pyth {
+            feed_id,
+            shard_id: 0,
+            price_type: PriceType::Spot,
+            equality: Equality::GreaterThanOrEqual,
+            limit,
+            limit_exponent: 0,
+            max_age: 30,
+            max_confidence_bps: None,
+        };
+
+        // The limit is compared in whole units rather than in the exponent of the feed.
+        assert!(condition_holds(&condition(123), &[account.clone()], &clock).unwrap());
+        assert!(!condition_holds(&condition(124), &[account.clone()], &clock).unwrap());
+    }
+
+    #[test]
+    fn test_legacy_pyth_trigger_normalizes_limit() {
+        let feed_id = [1; 32];
+        let key = get_oracle_key(0, feed_id);
+        let mut lamports = 0;
+        let mut data = price_update_data(feed_id);
+        let account = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &pyth::ID,
+            false,
+            0,
+        );
+        let mut thread = guarded_thread(Pubkey::new_unique());
+        thread.exec_context = None;
+        let clock = Clock {
+            unix_timestamp: 60,
+            ..Clock::default()
+        };
+
+        // The limit is in whole units, so 124 isn't met by a price of 123.45.
+        thread.trigger = Trigger::Pyth {
+            feed_id,
+            equality: Equality::GreaterThanOrEqual,
+            limit: 124,
+        };
+        assert!(activate_trigger(&mut thread, &key, &[account.clone()], &clock).is_err());
+
+        thread.trigger = Trigger::Pyth {
+            feed_id,
+            equality: Equality::GreaterThanOrEqual,
+            limit: 123,
+        };
+        assert!(activate_trigger(&mut thread, &key, &[account.clone()], &clock).is_ok());
+        assert!(matches!(
+            thread.exec_context.unwrap().trigger_context,
+            TriggerContext::Pyth { price: 12_345 }
+        ));
+    }
+}
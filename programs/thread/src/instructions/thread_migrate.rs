@@ -0,0 +1,60 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use sablier_utils::account::AccountInfoExt;
+
+use crate::{errors::*, state::*};
+
+/// Accounts required by the `thread_migrate` instruction.
+#[derive(Accounts)]
+pub struct ThreadMigrate<'info> {
+    /// The payer of the reallocation, if the migrated thread needs more rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The Solana system program.
+    pub system_program: Program<'info, System>,
+
+    /// The thread to be migrated, in the `Thread` layout.
+    /// CHECK: The thread is deserialized and rewritten during instruction processing, since its layout changes.
+    #[account(mut, owner = crate::ID @ SablierError::InvalidThreadAccount)]
+    pub thread: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ThreadMigrate>) -> Result<()> {
+    // Get accounts
+    let payer = &ctx.accounts.payer;
+    let system_program = &ctx.accounts.system_program;
+    let thread_account = &ctx.accounts.thread;
+
+    // Deserialize the thread in its legacy layout, which verifies its discriminator.
+    let thread = Thread::try_deserialize(&mut thread_account.try_borrow_data()?.as_ref())?;
+    let thread = ThreadV2::from(thread);
+
+    // Realloc the account to the new layout.
+    let mut data = Vec::new();
+    thread.try_serialize(&mut data)?;
+    thread_account.realloc(data.len(), false)?;
+
+    // Top up the rent of the account if the new layout is larger.
+    let minimum_rent = Rent::get()?.minimum_balance(data.len());
+    let top_up = minimum_rent.saturating_sub(thread_account.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: thread_account.to_account_info(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    // Write the thread in the new layout.
+    thread_account.try_borrow_mut_data()?.copy_from_slice(&data);
+
+    Ok(())
+}
@@ -21,7 +21,7 @@ pub struct ThreadPause<'info> {
         bump = thread.bump,
         has_one = authority
     )]
-    pub thread: Account<'info, Thread>,
+    pub thread: Account<'info, ThreadV2>,
 }
 
 pub fn handler(ctx: Context<ThreadPause>) -> Result<()> {
@@ -29,7 +29,10 @@ pub fn handler(ctx: Context<ThreadPause>) -> Result<()> {
     let thread = &mut ctx.accounts.thread;
 
     // Pause the thread
-    thread.paused = true;
+    thread.pause(PauseReason::Authority);
+
+    // Realloc the thread account
+    thread.realloc_account()?;
 
     Ok(())
 }
@@ -21,7 +21,7 @@ pub struct ThreadReset<'info> {
         bump = thread.bump,
         has_one = authority
     )]
-    pub thread: Account<'info, Thread>,
+    pub thread: Account<'info, ThreadV2>,
 }
 
 pub fn handler(ctx: Context<ThreadReset>) -> Result<()> {
@@ -33,5 +33,8 @@ pub fn handler(ctx: Context<ThreadReset>) -> Result<()> {
     thread.exec_context = None;
     thread.created_at = Clock::get()?.into();
 
+    // Realloc the thread account
+    thread.realloc_account()?;
+
     Ok(())
 }
@@ -21,7 +21,7 @@ pub struct ThreadResume<'info> {
         bump = thread.bump,
         has_one = authority
     )]
-    pub thread: Account<'info, Thread>,
+    pub thread: Account<'info, ThreadV2>,
 }
 
 pub fn handler(ctx: Context<ThreadResume>) -> Result<()> {
@@ -30,19 +30,24 @@ pub fn handler(ctx: Context<ThreadResume>) -> Result<()> {
 
     // Resume the thread
     thread.paused = false;
+    thread.extension.pause_reason = None;
 
     // Update the exec context
     if let Some(exec_context) = thread.exec_context {
-        if let TriggerContext::Cron { started_at: _ } = exec_context.trigger_context {
+        if let TriggerContext::Cron { .. } = exec_context.trigger_context {
             // Jump ahead to the current timestamp
             thread.exec_context = Some(ExecContext {
                 trigger_context: TriggerContext::Cron {
                     started_at: Clock::get()?.unix_timestamp,
+                    skipped: 0,
                 },
                 ..exec_context
             });
         }
     }
 
+    // Realloc the thread account
+    thread.realloc_account()?;
+
     Ok(())
 }
@@ -0,0 +1,71 @@
+use {
+    crate::{constants::*, errors::*, state::*},
+    anchor_lang::prelude::*,
+};
+
+/// Accounts required by the `thread_signal` instruction.
+#[derive(Accounts)]
+pub struct ThreadSignal<'info> {
+    /// The thread authority, or the signaler designated by the thread's trigger.
+    pub signer: Signer<'info>,
+
+    /// The thread to be signaled.
+    #[account(
+        mut,
+        seeds = [
+            SEED_THREAD,
+            thread.authority.as_ref(),
+            thread.id.as_slice(),
+            thread.domain.as_ref().unwrap_or(&Vec::new()).as_slice()
+        ],
+        bump = thread.bump,
+    )]
+    pub thread: Account<'info, ThreadV2>,
+}
+
+pub fn handler(ctx: Context<ThreadSignal>, payload: u64) -> Result<()> {
+    // Get accounts
+    let signer = &ctx.accounts.signer;
+    let thread = &mut ctx.accounts.thread;
+
+    // Verify the signer is allowed to signal the thread.
+    let Trigger::Signal { signaler } = thread.trigger.primary() else {
+        return Err(SablierError::InvalidSignaler.into());
+    };
+    require!(
+        signer.key().eq(&thread.authority) || Some(signer.key()).eq(signaler),
+        SablierError::InvalidSignaler
+    );
+
+    // Record the signal. The thread will be kicked off for all the signals received since its last kickoff.
+    thread.exec_context = Some(match thread.exec_context {
+        None => ExecContext {
+            exec_index: 0,
+            execs_since_slot: 0,
+            last_exec_at: 0,
+            trigger_context: TriggerContext::Signal {
+                signals: 1,
+                handled: 0,
+                payload,
+            },
+        },
+        Some(exec_context) => match exec_context.trigger_context {
+            TriggerContext::Signal {
+                signals, handled, ..
+            } => ExecContext {
+                trigger_context: TriggerContext::Signal {
+                    signals: signals.saturating_add(1),
+                    handled,
+                    payload,
+                },
+                ..exec_context
+            },
+            _ => return Err(SablierError::InvalidThreadState.into()),
+        },
+    });
+
+    // Realloc the thread account
+    thread.realloc_account()?;
+
+    Ok(())
+}
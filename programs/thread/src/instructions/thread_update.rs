@@ -32,7 +32,7 @@ pub struct ThreadUpdate<'info> {
             bump = thread.bump,
             has_one = authority,
         )]
-    pub thread: Account<'info, Thread>,
+    pub thread: Account<'info, ThreadV2>,
 }
 
 pub fn handler(ctx: Context<ThreadUpdate>, settings: ThreadSettings) -> Result<()> {
@@ -51,6 +51,15 @@ pub fn handler(ctx: Context<ThreadUpdate>, settings: ThreadSettings) -> Result<(
         thread.instructions = instructions;
     }
 
+    // If provided, update the thread's name.
+    if let Some(name) = settings.name {
+        require!(
+            name.len() <= MAX_THREAD_NAME_LEN,
+            SablierError::ThreadNameTooLong
+        );
+        thread.name = Some(name);
+    }
+
     // If provided, update the rate limit.
     if let Some(rate_limit) = settings.rate_limit {
         thread.rate_limit = rate_limit;
@@ -59,17 +68,21 @@ pub fn handler(ctx: Context<ThreadUpdate>, settings: ThreadSettings) -> Result<(
     // If provided, update the thread's trigger and reset the exec context.
     if let Some(trigger) = settings.trigger {
         // Require the thread is not in the middle of processing.
+        // The trigger context is kept, so the primary trigger must keep its variant.
         require!(
-            std::mem::discriminant(&thread.trigger) == std::mem::discriminant(&trigger),
+            std::mem::discriminant(thread.trigger.primary())
+                == std::mem::discriminant(trigger.primary()),
             SablierError::InvalidTriggerVariant
         );
+        require!(trigger.is_valid(), SablierError::InvalidTrigger);
+        require!(!trigger.is_deprecated(), SablierError::DeprecatedTrigger);
         thread.trigger = trigger.clone();
 
         // If the user updates an account trigger, the trigger context is no longer valid.
         // Here we reset the trigger context to zero to re-prime the trigger.
         if thread.exec_context.is_some() {
             thread.exec_context = Some(ExecContext {
-                trigger_context: match trigger {
+                trigger_context: match trigger.primary() {
                     Trigger::Account {
                         address: _,
                         offset: _,
@@ -82,6 +95,66 @@ pub fn handler(ctx: Context<ThreadUpdate>, settings: ThreadSettings) -> Result<(
         }
     }
 
+    // If provided, update the thread's lifetime and restart counting its executions.
+    if let Some(lifetime) = settings.lifetime {
+        require!(lifetime.is_valid(), SablierError::InvalidThreadLifetime);
+        thread.extension.lifetime = lifetime;
+        thread.extension.executions = 0;
+    }
+
+    // If provided, update the thread's budget and restart counting its spending.
+    if let Some(budget) = settings.budget {
+        require!(budget.is_valid(), SablierError::InvalidThreadBudget);
+        thread.extension.budget = budget;
+        thread.extension.budget_window_started_at = Clock::get()?.unix_timestamp;
+        thread.extension.budget_spent = 0;
+    }
+
+    // If provided, update the thread's reserve.
+    if let Some(reserve) = settings.reserve {
+        thread.extension.reserve = reserve;
+    }
+
+    // If provided, update the options of cron threads.
+    if let Some(cron) = settings.cron {
+        require!(
+            cron.max_catch_up
+                .map_or(true, |max_catch_up| max_catch_up < CRON_CATCH_UP_SCAN_LIMIT),
+            SablierError::MaxCatchUpExceeded
+        );
+        thread.extension.cron = cron;
+    }
+
+    // If provided, update the maximum compute unit price of the priority fees reimbursed to workers.
+    if let Some(compute_unit_price) = settings.compute_unit_price {
+        thread.extension.compute_unit_price = compute_unit_price;
+    }
+
+    // If provided, update the address lookup tables the thread's transactions are compiled with.
+    if let Some(address_lookup_tables) = settings.address_lookup_tables {
+        require!(
+            address_lookup_tables.len() <= MAX_ADDRESS_LOOKUP_TABLES,
+            SablierError::MaxAddressLookupTablesExceeded
+        );
+        thread.extension.address_lookup_tables = address_lookup_tables;
+    }
+
+    // If provided, update the moments excluded from the schedule of cron threads.
+    if let Some(cron_exclusions) = settings.cron_exclusions {
+        thread.extension.cron_exclusions = cron_exclusions;
+    }
+
+    // Verify the cron schedules, if any, are written in the accepted dialects, and compile them.
+    let thread_pubkey = thread.key();
+    thread.extension.compiled_schedule = thread
+        .extension
+        .cron
+        .compile(&thread.trigger, &thread_pubkey)?;
+    thread.extension.compiled_exclusions = thread
+        .extension
+        .cron
+        .compile_exclusions(&thread.extension.cron_exclusions, &thread_pubkey)?;
+
     // Reallocate mem for the thread account
     thread.realloc_account()?;
 
@@ -1,6 +1,7 @@
 use {
     crate::{constants::*, errors::*, state::*},
     anchor_lang::prelude::*,
+    sablier_utils::account::AccountInfoExt,
 };
 
 /// Accounts required by the `thread_withdraw` instruction.
@@ -26,7 +27,7 @@ pub struct ThreadWithdraw<'info> {
         bump = thread.bump,
         has_one = authority,
     )]
-    pub thread: Account<'info, Thread>,
+    pub thread: Account<'info, ThreadV2>,
 }
 
 pub fn handler(ctx: Context<ThreadWithdraw>, amount: u64) -> Result<()> {
@@ -34,8 +35,11 @@ pub fn handler(ctx: Context<ThreadWithdraw>, amount: u64) -> Result<()> {
     let pay_to = &mut ctx.accounts.pay_to;
     let thread = &mut ctx.accounts.thread;
 
+    // Realloc the thread account
+    thread.realloc_account()?;
+
     // Calculate the minimum rent threshold
-    let data_len = 8 + thread.try_to_vec()?.len();
+    let data_len = thread.data_len();
     let minimum_rent = Rent::get()?.minimum_balance(data_len);
     let post_balance = thread.get_lamports() - amount;
 
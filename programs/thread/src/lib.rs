@@ -52,6 +52,11 @@ pub mod thread_program {
         thread_delete::handler(ctx)
     }
 
+    /// Deposits lamports into a thread's balance, e.g. to fund a thread paused for its low balance.
+    pub fn thread_deposit(ctx: Context<ThreadDeposit>, amount: u64) -> Result<()> {
+        thread_deposit::handler(ctx, amount)
+    }
+
     /// Appends a new instruction to the thread's instruction set.
     pub fn thread_instruction_add(
         ctx: Context<ThreadInstructionAdd>,
@@ -73,6 +78,11 @@ pub mod thread_program {
         thread_kickoff::handler(ctx)
     }
 
+    /// Converts a thread created before `ThreadV2` to the new layout, in place.
+    pub fn thread_migrate(ctx: Context<ThreadMigrate>) -> Result<()> {
+        thread_migrate::handler(ctx)
+    }
+
     /// Pauses an active thread.
     pub fn thread_pause(ctx: Context<ThreadPause>) -> Result<()> {
         thread_pause::handler(ctx)
@@ -88,6 +98,11 @@ pub mod thread_program {
         thread_reset::handler(ctx)
     }
 
+    /// Signals a thread with a signal trigger, allowing it to be kicked off.
+    pub fn thread_signal(ctx: Context<ThreadSignal>, payload: u64) -> Result<()> {
+        thread_signal::handler(ctx, payload)
+    }
+
     /// Allows an owner to update the mutable properties of a thread.
     pub fn thread_update(ctx: Context<ThreadUpdate>, settings: ThreadSettings) -> Result<()> {
         thread_update::handler(ctx, settings)
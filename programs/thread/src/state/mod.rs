@@ -1,8 +1,10 @@
 //! All objects needed to describe and manage the program's state.
 
 mod thread;
+mod thread_v1;
 mod versioned_thread;
 
 pub use sablier_utils::thread::*;
 pub use thread::*;
+pub use thread_v1::*;
 pub use versioned_thread::*;
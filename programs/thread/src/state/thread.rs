@@ -1,16 +1,31 @@
 use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
+use anchor_lang::{
+    solana_program::{instruction::Instruction, sysvar},
+    InstructionData, ToAccountMetas,
+};
+use chrono::{DateTime, Days, NaiveDate};
+use sablier_cron::{Calendar, CompiledSchedule, Dates, Dialect, Schedule};
 use sablier_utils::{
-    account::AccountInfoExt,
-    thread::{ClockData, SerializableInstruction, Trigger},
+    account::{AccountExtension, AccountInfoExt},
+    thread::{ClockData, SerializableInstruction, Trigger, PRIMARY_TRIGGER_SPACE},
     MinSpace, Space,
 };
+use solana_program::pubkey;
 
-use crate::constants::{NEXT_INSTRUCTION_SIZE, SEED_THREAD};
+use crate::{
+    constants::{
+        COMPILED_SCHEDULE_LEN, MAX_CRON_EXCLUDED_DATES, SEED_THREAD,
+        TRANSACTION_BASE_FEE_REIMBURSEMENT,
+    },
+    errors::SablierError,
+};
 
 /// Tracks the current state of a transaction thread on Solana.
+///
+/// Threads created before this layout are `Thread` accounts, which `thread_migrate` converts in place.
 #[account]
 #[derive(Debug)]
-pub struct Thread {
+pub struct ThreadV2 {
     /// The owner of this thread.
     pub authority: Pubkey,
     /// The bump, used for PDA validation.
@@ -26,6 +41,8 @@ pub struct Thread {
     pub id: Vec<u8>,
     /// The instructions to be executed.
     pub instructions: Vec<SerializableInstruction>,
+    /// The name of the thread, given by the authority.
+    pub name: Option<String>,
     /// The next instruction to be executed.
     pub next_instruction: Option<SerializableInstruction>,
     /// Whether or not the thread is currently paused.
@@ -34,9 +51,11 @@ pub struct Thread {
     pub rate_limit: u64,
     /// The triggering event to kickoff a thread.
     pub trigger: Trigger,
+    /// The properties added to the thread layout after its initial release.
+    pub extension: AccountExtension<ThreadExtension>,
 }
 
-impl Thread {
+impl ThreadV2 {
     /// Derive the pubkey of a thread account.
     pub fn pubkey(authority: Pubkey, id: Vec<u8>, domain: Option<Vec<u8>>) -> Pubkey {
         Pubkey::find_program_address(
@@ -52,13 +71,13 @@ impl Thread {
     }
 }
 
-impl PartialEq for Thread {
+impl PartialEq for ThreadV2 {
     fn eq(&self, other: &Self) -> bool {
         self.authority.eq(&other.authority) && self.id.eq(&other.id)
     }
 }
 
-impl Eq for Thread {}
+impl Eq for ThreadV2 {}
 
 /// Trait for reading and writing to a thread account.
 pub trait ThreadAccount {
@@ -67,12 +86,96 @@ pub trait ThreadAccount {
 
     /// Allocate more memory for the account.
     fn realloc_account(&mut self) -> Result<()>;
+
+    /// Get the instruction closing the thread account to `close_to`, signed by the thread itself.
+    fn delete_instruction(&self, close_to: Pubkey) -> SerializableInstruction;
+
+    /// Get the number of lamports of the thread above the rent-exempt minimum.
+    fn available_lamports(&self) -> Result<u64>;
+
+    /// Returns true if spending `lamports` would bring the balance of the thread below its reserve.
+    fn is_below_reserve(&self, lamports: u64) -> Result<bool>;
+
+    /// Get the number of lamports the thread can spend without bringing its balance below its reserve.
+    fn spendable_lamports(&self) -> Result<u64>;
 }
 
-impl Thread {
-    pub fn min_space(instructions: &[SerializableInstruction]) -> Result<usize> {
+impl ThreadV2 {
+    /// Returns the slot at which the thread last completed an execution cycle, if it is not running one.
+    /// If `require_last_instruction` is true, cycles which did not reach the last instruction of the instruction set don't count.
+    pub fn completed_cycle_at(&self, require_last_instruction: bool) -> Option<u64> {
+        if self.next_instruction.is_some() {
+            return None;
+        }
+        let exec_context = self.exec_context?;
+        if require_last_instruction && exec_context.exec_index + 1 < self.instructions.len() as u64
+        {
+            return None;
+        }
+        Some(exec_context.last_exec_at)
+    }
+
+    /// Returns true if the thread cannot start a new execution cycle anymore,
+    /// because it passed the end of its execution window or reached its maximum number of executions.
+    pub fn is_expired(&self, unix_timestamp: i64) -> bool {
+        self.is_past_window(unix_timestamp)
+            || self
+                .extension
+                .lifetime
+                .is_exhausted(self.extension.executions)
+    }
+
+    /// Returns true if the thread passed the end of its execution window.
+    pub fn is_past_window(&self, unix_timestamp: i64) -> bool {
+        self.extension
+            .lifetime
+            .not_after
+            .is_some_and(|not_after| unix_timestamp > not_after)
+    }
+
+    /// Returns the fees of the current transaction to reimburse to the signatory: its base fee and its priority fee,
+    /// at a compute unit price of at most `compute_unit_price`.
+    ///
+    /// The priority fee is read from the compute budget instructions of the transaction, through the instructions
+    /// sysvar passed among `remaining_accounts`. It is only reimbursed by the first instruction of the program in the
+    /// transaction, so that threads kicked off or executed together don't reimburse it twice.
+    pub fn transaction_fee(&self, remaining_accounts: &[AccountInfo]) -> Result<u64> {
+        let compute_unit_price = self.extension.compute_unit_price;
+        let instructions = remaining_accounts
+            .iter()
+            .find(|account| sysvar::instructions::check_id(account.key));
+        let priority_fee = match instructions {
+            Some(instructions) if compute_unit_price > 0 => {
+                priority_fee(instructions, compute_unit_price)?
+            }
+            _ => 0,
+        };
+        Ok(TRANSACTION_BASE_FEE_REIMBURSEMENT.saturating_add(priority_fee))
+    }
+
+    /// Pauses the thread, recording why.
+    pub fn pause(&mut self, reason: PauseReason) {
+        msg!("Pausing thread: {:?}", reason);
+        self.paused = true;
+        self.extension.pause_reason = Some(reason);
+    }
+
+    pub fn min_space(instructions: &[SerializableInstruction], trigger: &Trigger) -> Result<usize> {
         let ins_space = instructions.try_to_vec()?.len();
 
+        // Reserve space for the largest instruction of the set as the next instruction.
+        // The account is reallocated when a larger dynamic instruction is set.
+        let next_ins_space = instructions.iter().try_fold(0, |space, ix| {
+            ix.try_to_vec().map(|data| space.max(data.len()))
+        })?;
+
+        // Compound triggers can be much larger than other triggers, so only reserve space for their actual size.
+        // Cron schedules longer than `MAX_CRON_SCHEDULE_LEN` may also exceed the space reserved for primary triggers.
+        let trigger_space = match trigger {
+            Trigger::Compound { .. } => trigger.try_to_vec()?.len(),
+            _ => PRIMARY_TRIGGER_SPACE.max(trigger.try_to_vec()?.len()),
+        };
+
         Ok(
             8
             + Pubkey::MIN_SPACE // authority
@@ -83,17 +186,19 @@ impl Thread {
             + u64::MIN_SPACE // fee
             + (4 + 32) // id
             + (4 + ins_space) // instructions
-            + (1 + NEXT_INSTRUCTION_SIZE) // next_instruction
+            + 1 // name, reallocated when set
+            + (1 + next_ins_space) // next_instruction
             + bool::MIN_SPACE // paused
             + u64::MIN_SPACE // rate_limit
-            + Trigger::MIN_SPACE, // trigger
+            + trigger_space // trigger
+            + <AccountExtension<ThreadExtension>>::MIN_SPACE, // extension
         )
     }
 }
 
-impl ThreadAccount for Account<'_, Thread> {
+impl ThreadAccount for Account<'_, ThreadV2> {
     fn pubkey(&self) -> Pubkey {
-        Thread::pubkey(self.authority, self.id.clone(), self.domain.clone())
+        ThreadV2::pubkey(self.authority, self.id.clone(), self.domain.clone())
     }
 
     fn realloc_account(&mut self) -> Result<()> {
@@ -103,6 +208,387 @@ impl ThreadAccount for Account<'_, Thread> {
         self.realloc(data_len, false)?;
         Ok(())
     }
+
+    fn delete_instruction(&self, close_to: Pubkey) -> SerializableInstruction {
+        Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::ThreadDelete {
+                authority: self.key(),
+                close_to,
+                thread: self.key(),
+            }
+            .to_account_metas(Some(true)),
+            data: crate::instruction::ThreadDelete {}.data(),
+        }
+        .into()
+    }
+
+    fn available_lamports(&self) -> Result<u64> {
+        let minimum_rent = Rent::get()?.minimum_balance(self.to_account_info().data_len());
+        Ok(self.get_lamports().saturating_sub(minimum_rent))
+    }
+
+    fn is_below_reserve(&self, lamports: u64) -> Result<bool> {
+        let required = lamports.saturating_add(self.extension.reserve);
+        Ok(self.available_lamports()? < required)
+    }
+
+    fn spendable_lamports(&self) -> Result<u64> {
+        Ok(self
+            .available_lamports()?
+            .saturating_sub(self.extension.reserve))
+    }
+}
+
+/// The compute budget program, whose instructions set the priority fee of transactions.
+const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// Returns the priority fee of the current transaction, at a compute unit price of at most `max_compute_unit_price`,
+/// or 0 if an instruction of the program precedes the current one.
+fn priority_fee(instructions: &AccountInfo, max_compute_unit_price: u64) -> Result<u64> {
+    let current_index = sysvar::instructions::load_current_index_checked(instructions)?;
+    let mut compute_unit_limit = None;
+    let mut compute_unit_price = 0;
+    let mut index = 0;
+    while let Ok(instruction) =
+        sysvar::instructions::load_instruction_at_checked(index, instructions)
+    {
+        // Migrations precede the first instruction of a thread in a transaction, and reimburse nothing.
+        let is_migration = instruction.data == crate::instruction::ThreadMigrate {}.data();
+        if instruction.program_id == crate::ID && index < current_index as usize && !is_migration {
+            return Ok(0);
+        }
+        if instruction.program_id == COMPUTE_BUDGET_PROGRAM_ID {
+            // The borsh tags of `SetComputeUnitLimit` and `SetComputeUnitPrice`.
+            match instruction.data.split_first() {
+                Some((2, limit)) => {
+                    compute_unit_limit = limit.try_into().ok().map(u32::from_le_bytes);
+                }
+                Some((3, price)) => {
+                    compute_unit_price = price.try_into().map_or(0, u64::from_le_bytes);
+                }
+                _ => {}
+            }
+        }
+        index += 1;
+    }
+
+    // Transactions without a compute unit limit are charged for a default limit, which is not reimbursed.
+    let Some(compute_unit_limit) = compute_unit_limit else {
+        return Ok(0);
+    };
+    let compute_unit_price = compute_unit_price.min(max_compute_unit_price) as u128;
+    let priority_fee = (compute_unit_price * compute_unit_limit as u128).div_ceil(1_000_000);
+    Ok(priority_fee.try_into().unwrap_or(u64::MAX))
+}
+
+/// The properties of a thread which were added after its initial release.
+#[derive(AnchorDeserialize, AnchorSerialize, MinSpace, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ThreadExtension {
+    /// The window and number of executions the thread is allowed to run.
+    pub lifetime: ThreadLifetime,
+
+    /// The number of execution cycles started since the lifetime was set.
+    pub executions: u64,
+
+    /// The options of threads with a cron trigger.
+    pub cron: CronOptions,
+
+    /// The schedule of the cron trigger, compiled when the trigger is set so kickoffs don't parse it.
+    /// `None` for other triggers, and for cron threads whose trigger was last set before schedules were compiled.
+    pub compiled_schedule: Option<[u8; COMPILED_SCHEDULE_LEN]>,
+
+    /// The moments excluded from the schedule of a cron thread.
+    pub cron_exclusions: CronExclusions,
+
+    /// The schedule of the cron exclusions, compiled when they are set.
+    pub compiled_exclusions: Option<[u8; COMPILED_SCHEDULE_LEN]>,
+
+    /// The maximum compute unit price, in micro-lamports, of the priority fees reimbursed to workers.
+    /// Workers add a priority fee at this price to the transactions of the thread. 0 for none.
+    pub compute_unit_price: u64,
+
+    /// The address lookup tables workers compile the transactions of the thread with,
+    /// so that instructions touching many accounts fit in a transaction.
+    #[raw_space(4)]
+    pub address_lookup_tables: Vec<Pubkey>,
+
+    /// The caps on the lamports the thread spends on kickoffs and execs.
+    pub budget: ThreadBudget,
+
+    /// The unix timestamp at which the current budget window started.
+    pub budget_window_started_at: i64,
+
+    /// The number of lamports spent on kickoffs and execs since the current budget window started.
+    pub budget_spent: u64,
+
+    /// The number of lamports, above the rent-exempt minimum, the thread keeps in reserve.
+    /// The thread pauses itself when its balance would fall below its reserve.
+    pub reserve: u64,
+
+    /// Why the thread was paused, if it is paused.
+    pub pause_reason: Option<PauseReason>,
+}
+
+impl ThreadExtension {
+    /// Returns the compiled schedule of a cron thread.
+    /// Threads whose trigger was set before schedules were compiled have their schedule compiled on the fly,
+    /// with its 'H' tokens hashed with the pubkey of the thread.
+    pub fn cron_schedule(
+        &self,
+        schedule: &str,
+        thread_pubkey: &Pubkey,
+    ) -> Result<CompiledSchedule> {
+        match self.compiled_schedule {
+            Some(bytes) => CompiledSchedule::from_bytes(&bytes),
+            None => Schedule::parse_seeded(schedule, thread_pubkey.as_ref())
+                .map(|schedule| schedule.compile()),
+        }
+        .map_err(|err| {
+            msg!("{}", err);
+            SablierError::InvalidSchedule.into()
+        })
+    }
+
+    /// Records lamports spent on a kickoff or an exec in the current budget window,
+    /// starting a new window if the current one is over. Returns true if the budget of the window is exhausted.
+    pub fn record_spending(&mut self, lamports: u64, unix_timestamp: i64) -> bool {
+        if unix_timestamp
+            >= self
+                .budget_window_started_at
+                .saturating_add(self.budget.window)
+        {
+            self.budget_window_started_at = unix_timestamp;
+            self.budget_spent = 0;
+        }
+        self.budget_spent = self.budget_spent.saturating_add(lamports);
+        self.budget
+            .max_spending
+            .is_some_and(|max_spending| self.budget_spent >= max_spending)
+    }
+
+    /// Returns the moments of the schedule of a cron thread, without its excluded dates and moments.
+    pub fn cron_calendar(&self, schedule: &str, thread_pubkey: &Pubkey) -> Result<Calendar> {
+        let schedule = self.cron_schedule(schedule, thread_pubkey)?;
+        let mut calendar = Calendar::from(schedule);
+        if !self.cron_exclusions.dates.is_empty() {
+            // Dates start and end in the timezone of the schedule.
+            let dates = Dates::new(self.cron_exclusions.dates()).in_timezone(schedule.timezone());
+            calendar = calendar.except(dates);
+        }
+        if let Some(bytes) = self.compiled_exclusions {
+            let excluded = CompiledSchedule::from_bytes(&bytes).map_err(|err| {
+                msg!("{}", err);
+                SablierError::InvalidSchedule
+            })?;
+            calendar = calendar.except(excluded);
+        }
+        Ok(calendar)
+    }
+}
+
+/// The options of threads with a cron trigger.
+#[derive(
+    AnchorDeserialize, AnchorSerialize, MinSpace, Clone, Copy, Debug, Default, PartialEq, Eq,
+)]
+pub struct CronOptions {
+    /// The maximum number of missed moments a non-skippable thread replays after a kickoff.
+    /// If more moments were missed, they are all coalesced into the kickoff. If `None`, every missed moment is replayed.
+    pub max_catch_up: Option<u64>,
+
+    /// The dialect the schedule must be written in. If `None`, any dialect is accepted.
+    pub dialect: Option<CronDialect>,
+}
+
+impl CronOptions {
+    /// Compiles the schedule of a cron trigger, requiring it to be written in the accepted dialects.
+    /// Its 'H' tokens are hashed with the pubkey of the thread. Returns `None` for other triggers.
+    pub fn compile(
+        &self,
+        trigger: &Trigger,
+        thread_pubkey: &Pubkey,
+    ) -> Result<Option<[u8; COMPILED_SCHEDULE_LEN]>> {
+        match trigger.primary() {
+            Trigger::Cron { schedule, .. } => {
+                self.compile_schedule(schedule, thread_pubkey).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Verifies the cron exclusions and compiles their schedule, like the schedule of the trigger.
+    pub fn compile_exclusions(
+        &self,
+        exclusions: &CronExclusions,
+        thread_pubkey: &Pubkey,
+    ) -> Result<Option<[u8; COMPILED_SCHEDULE_LEN]>> {
+        require!(exclusions.is_valid(), SablierError::InvalidCronExclusions);
+        exclusions
+            .schedule
+            .as_ref()
+            .map(|schedule| self.compile_schedule(schedule, thread_pubkey))
+            .transpose()
+    }
+
+    fn compile_schedule(
+        &self,
+        schedule: &str,
+        thread_pubkey: &Pubkey,
+    ) -> Result<[u8; COMPILED_SCHEDULE_LEN]> {
+        let seed = thread_pubkey.as_ref();
+        let schedule = match self.dialect {
+            Some(dialect) => Schedule::parse_as_seeded(schedule, dialect.into(), seed),
+            None => Schedule::parse_seeded(schedule, seed),
+        }
+        .map_err(|err| {
+            // Log where the schedule is invalid, the error code alone doesn't tell.
+            msg!("{}", err);
+            SablierError::InvalidSchedule
+        })?;
+        Ok(schedule.compile().to_bytes())
+    }
+}
+
+/// The moments excluded from the schedule of a cron thread, e.g. holidays.
+#[derive(AnchorDeserialize, AnchorSerialize, MinSpace, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CronExclusions {
+    /// The dates on which the schedule doesn't fire, in days since 1970-01-01, sorted without duplicates.
+    /// They start and end in the timezone of the schedule.
+    #[raw_space(4)]
+    pub dates: Vec<u16>,
+
+    /// A cron schedule whose moments are excluded, e.g. `* * * * * Sat,Sun` to exclude weekends.
+    /// It is written in the dialect of the thread, and its 'H' tokens are hashed with the pubkey of the thread.
+    #[raw_space(1)]
+    pub schedule: Option<String>,
+}
+
+impl CronExclusions {
+    /// Returns true if the dates are sorted without duplicates and don't exceed `MAX_CRON_EXCLUDED_DATES`.
+    pub fn is_valid(&self) -> bool {
+        self.dates.len() <= MAX_CRON_EXCLUDED_DATES
+            && self.dates.windows(2).all(|pair| pair[0] < pair[1])
+    }
+
+    /// Returns the excluded dates.
+    pub fn dates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.dates.iter().filter_map(|days| {
+            DateTime::UNIX_EPOCH
+                .date_naive()
+                .checked_add_days(Days::new(*days as u64))
+        })
+    }
+}
+
+/// The dialects of cron schedules, told apart by their number of fields.
+#[derive(AnchorDeserialize, AnchorSerialize, MinSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CronDialect {
+    /// `min hour day-of-month month day-of-week`, as in Unix crontabs.
+    Unix,
+    /// `sec min hour day-of-month month day-of-week`
+    Seconds,
+    /// `sec min hour day-of-month month day-of-week year`
+    Years,
+}
+
+impl From<Dialect> for CronDialect {
+    fn from(dialect: Dialect) -> Self {
+        match dialect {
+            Dialect::Unix => CronDialect::Unix,
+            Dialect::Seconds => CronDialect::Seconds,
+            Dialect::Years => CronDialect::Years,
+        }
+    }
+}
+
+impl From<CronDialect> for Dialect {
+    fn from(dialect: CronDialect) -> Self {
+        match dialect {
+            CronDialect::Unix => Dialect::Unix,
+            CronDialect::Seconds => Dialect::Seconds,
+            CronDialect::Years => Dialect::Years,
+        }
+    }
+}
+
+/// The execution window and maximum number of executions of a thread.
+///
+/// Once the thread passes the end of its window or reaches its maximum number of executions,
+/// it cannot be kicked off anymore and the next exec closes it to its authority.
+#[derive(
+    AnchorDeserialize, AnchorSerialize, MinSpace, Clone, Copy, Debug, Default, PartialEq, Eq,
+)]
+pub struct ThreadLifetime {
+    /// The unix timestamp before which the thread cannot be kicked off.
+    pub not_before: Option<i64>,
+
+    /// The unix timestamp after which the thread expires.
+    pub not_after: Option<i64>,
+
+    /// The number of execution cycles after which the thread expires.
+    pub max_executions: Option<u64>,
+}
+
+impl ThreadLifetime {
+    /// Returns true if the window is not empty.
+    pub fn is_valid(&self) -> bool {
+        match (self.not_before, self.not_after) {
+            (Some(not_before), Some(not_after)) => not_before <= not_after,
+            _ => true,
+        }
+    }
+
+    /// Returns true if the thread cannot be kicked off yet.
+    pub fn is_before_window(&self, unix_timestamp: i64) -> bool {
+        self.not_before
+            .is_some_and(|not_before| unix_timestamp < not_before)
+    }
+
+    /// Returns true if `executions` reached the maximum number of executions.
+    pub fn is_exhausted(&self, executions: u64) -> bool {
+        self.max_executions
+            .is_some_and(|max_executions| executions >= max_executions)
+    }
+}
+
+/// The caps on the lamports a thread spends on kickoffs and execs: transaction fees, worker fees and reimbursements
+/// of the lamports inner instructions spend from the signatory.
+///
+/// Once the thread spends its budget in a window, it is paused until its authority resumes it.
+/// The exec exhausting the budget may exceed it.
+#[derive(
+    AnchorDeserialize, AnchorSerialize, MinSpace, Clone, Copy, Debug, Default, PartialEq, Eq,
+)]
+pub struct ThreadBudget {
+    /// The maximum number of lamports an inner instruction may spend from the signatory, reimbursed by the thread.
+    /// Execs spending more are only reimbursed this much, and pause the thread.
+    pub max_reimbursement: Option<u64>,
+
+    /// The maximum number of lamports the thread spends per window.
+    pub max_spending: Option<u64>,
+
+    /// The length of the budget window, in seconds, e.g. `86_400` for a daily budget.
+    pub window: i64,
+}
+
+impl ThreadBudget {
+    /// Returns true if the budget spending is capped over a non-empty window.
+    pub fn is_valid(&self) -> bool {
+        self.max_spending.is_none() || self.window > 0
+    }
+}
+
+/// The reasons a thread is paused for.
+#[derive(AnchorDeserialize, AnchorSerialize, MinSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseReason {
+    /// The authority paused the thread.
+    Authority,
+    /// The thread spent its budget for the current window.
+    BudgetExhausted,
+    /// The balance of the thread fell below its reserve. Deposit lamports into the thread before resuming it.
+    LowBalance,
+    /// An inner instruction spent more lamports from the signatory than the thread reimburses.
+    ReimbursementCapExceeded,
 }
 
 /// The execution context of a particular transaction thread.
@@ -111,10 +597,6 @@ pub struct ExecContext {
     /// Index of the next instruction to be executed.
     pub exec_index: u64,
 
-    /// Number of execs since the last tx reimbursement.
-    /// To be deprecated in v3 since we now reimburse for every transaction.
-    pub execs_since_reimbursement: u64,
-
     /// Number of execs in this slot.
     pub execs_since_slot: u64,
 
@@ -138,6 +620,8 @@ pub enum TriggerContext {
     Cron {
         /// The threshold moment the schedule was waiting for.
         started_at: i64,
+        /// The number of missed moments coalesced into the kickoff, saturating at `CRON_CATCH_UP_SCAN_LIMIT`.
+        skipped: u64,
     },
 
     /// The trigger context for threads with a "now" trigger.
@@ -161,7 +645,8 @@ pub enum TriggerContext {
         started_at: i64,
     },
 
-    /// The trigger context for threads with a "pyth" trigger.
+    /// The trigger context for threads with a "pyth" or "pyth price" trigger.
+    /// The price is the compared price (spot or EMA) at kickoff, in the exponent of the feed.
     Pyth { price: i64 },
 
     /// The trigger context for threads with a periodic timestamp trigger.
@@ -169,6 +654,53 @@ pub enum TriggerContext {
         /// The threshold moment the schedule was waiting for.
         started_at: i64,
     },
+
+    /// The trigger context for threads with an "account value" trigger.
+    AccountValue {
+        /// The value read from the account at kickoff.
+        value: i128,
+    },
+
+    /// The trigger context for threads with a "token balance" trigger.
+    TokenBalance {
+        /// The token account balance at kickoff.
+        amount: u64,
+    },
+
+    /// The trigger context for threads with an "oracle" trigger.
+    Oracle {
+        /// The price at kickoff, in the exponent of the feed.
+        price: i128,
+    },
+
+    /// The trigger context for threads with a "chain" trigger.
+    Chain {
+        /// The slot at which the upstream thread completed the execution cycle which kicked off this thread.
+        completed_at: u64,
+    },
+
+    /// The trigger context for threads with a "signal" trigger.
+    Signal {
+        /// The number of signals received.
+        signals: u64,
+        /// The number of signals received up to the last kickoff.
+        handled: u64,
+        /// The payload of the last signal.
+        payload: u64,
+    },
+
+    /// The trigger context for threads with a "slot interval" trigger.
+    SlotInterval {
+        /// The threshold slot the schedule was waiting for.
+        started_at: u64,
+    },
+}
+
+impl TriggerContext {
+    /// Returns true if a signal was received since the last kickoff.
+    pub fn has_pending_signal(&self) -> bool {
+        matches!(self, TriggerContext::Signal { signals, handled, .. } if signals.gt(handled))
+    }
 }
 
 /// The properties of threads which are updatable.
@@ -179,4 +711,49 @@ pub struct ThreadSettings {
     pub name: Option<String>,
     pub rate_limit: Option<u64>,
     pub trigger: Option<Trigger>,
+    pub lifetime: Option<ThreadLifetime>,
+    pub cron: Option<CronOptions>,
+    pub cron_exclusions: Option<CronExclusions>,
+    pub compute_unit_price: Option<u64>,
+    pub address_lookup_tables: Option<Vec<Pubkey>>,
+    pub budget: Option<ThreadBudget>,
+    pub reserve: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_space_fits_cron_timezone() {
+        let trigger = Trigger::Cron {
+            schedule: "CRON_TZ=America/New_York 0 30 9 * * MON-FRI".into(),
+            skippable: true,
+        };
+        let thread = ThreadV2 {
+            authority: Pubkey::new_unique(),
+            bump: 255,
+            created_at: ClockData {
+                slot: 0,
+                epoch: 0,
+                unix_timestamp: 0,
+            },
+            domain: Some(vec![0; 32]),
+            exec_context: None,
+            fee: 0,
+            id: vec![0; 32],
+            instructions: vec![],
+            name: None,
+            next_instruction: None,
+            paused: false,
+            rate_limit: 1,
+            trigger: trigger.clone(),
+            extension: AccountExtension::default(),
+        };
+
+        // The schedule fits in the space reserved for primary triggers, so the thread needs no more.
+        let space = ThreadV2::min_space(&[], &trigger).unwrap();
+        assert_eq!(space, ThreadV2::min_space(&[], &Trigger::Now).unwrap());
+        assert!(8 + thread.try_to_vec().unwrap().len() <= space);
+    }
 }
@@ -0,0 +1,176 @@
+use std::io::Read;
+
+use anchor_lang::{prelude::*, AnchorDeserialize, AnchorSerialize};
+use sablier_utils::{
+    account::AccountExtension,
+    thread::{ClockData, SerializableInstruction, Trigger},
+    MinSpace, Space,
+};
+
+use crate::state::{ExecContext, ThreadExtension, ThreadV2, TriggerContext};
+
+/// The layout of threads created before `ThreadV2`, converted in place by `thread_migrate`.
+#[account]
+#[derive(Debug)]
+pub struct Thread {
+    /// The owner of this thread.
+    pub authority: Pubkey,
+    /// The bump, used for PDA validation.
+    pub bump: u8,
+    /// The cluster clock at the moment the thread was created.
+    pub created_at: ClockData,
+    pub domain: Option<Vec<u8>>,
+    /// The context of the thread's current execution state.
+    pub exec_context: Option<ExecContextV1>,
+    /// The number of lamports to payout to workers per execution.
+    pub fee: u64,
+    /// The id of the thread, given by the authority.
+    pub id: Vec<u8>,
+    /// The instructions to be executed.
+    pub instructions: Vec<SerializableInstruction>,
+    /// The next instruction to be executed.
+    pub next_instruction: Option<SerializableInstruction>,
+    /// Whether or not the thread is currently paused.
+    pub paused: bool,
+    /// The maximum number of execs allowed per slot.
+    pub rate_limit: u64,
+    /// The triggering event to kickoff a thread.
+    pub trigger: Trigger,
+    /// The properties added to the thread layout after its initial release.
+    pub extension: AccountExtension<ThreadExtension>,
+}
+
+impl Thread {
+    /// Derive the pubkey of a thread account.
+    pub fn pubkey(authority: Pubkey, id: Vec<u8>, domain: Option<Vec<u8>>) -> Pubkey {
+        ThreadV2::pubkey(authority, id, domain)
+    }
+}
+
+impl PartialEq for Thread {
+    fn eq(&self, other: &Self) -> bool {
+        self.authority.eq(&other.authority) && self.id.eq(&other.id)
+    }
+}
+
+impl Eq for Thread {}
+
+impl From<Thread> for ThreadV2 {
+    fn from(thread: Thread) -> Self {
+        ThreadV2 {
+            authority: thread.authority,
+            bump: thread.bump,
+            created_at: thread.created_at,
+            domain: thread.domain,
+            exec_context: thread.exec_context.map(Into::into),
+            fee: thread.fee,
+            id: thread.id,
+            instructions: thread.instructions,
+            name: None,
+            next_instruction: thread.next_instruction,
+            paused: thread.paused,
+            rate_limit: thread.rate_limit,
+            trigger: thread.trigger,
+            extension: thread.extension,
+        }
+    }
+}
+
+/// The execution context of `Thread` accounts.
+#[derive(AnchorDeserialize, AnchorSerialize, MinSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExecContextV1 {
+    /// Index of the next instruction to be executed.
+    pub exec_index: u64,
+
+    /// Number of execs since the last tx reimbursement.
+    /// Dropped by `ExecContext`, since every transaction is reimbursed.
+    pub execs_since_reimbursement: u64,
+
+    /// Number of execs in this slot.
+    pub execs_since_slot: u64,
+
+    /// Slot of the last exec
+    pub last_exec_at: u64,
+
+    /// Context for the triggering condition
+    pub trigger_context: TriggerContextV1,
+}
+
+impl From<ExecContextV1> for ExecContext {
+    fn from(exec_context: ExecContextV1) -> Self {
+        ExecContext {
+            exec_index: exec_context.exec_index,
+            execs_since_slot: exec_context.execs_since_slot,
+            last_exec_at: exec_context.last_exec_at,
+            trigger_context: exec_context.trigger_context.0,
+        }
+    }
+}
+
+/// The trigger context of `Thread` accounts, whose cron variant does not record the skipped moments.
+/// The other variants share the layout of `TriggerContext`, so only the cron variant is read and written by hand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TriggerContextV1(pub TriggerContext);
+
+/// The index of the cron variant of `TriggerContext`.
+const CRON_VARIANT: u8 = 1;
+
+impl AnchorSerialize for TriggerContextV1 {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self.0 {
+            TriggerContext::Cron { started_at, .. } => {
+                CRON_VARIANT.serialize(writer)?;
+                started_at.serialize(writer)
+            }
+            trigger_context => trigger_context.serialize(writer),
+        }
+    }
+}
+
+impl AnchorDeserialize for TriggerContextV1 {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        let variant = u8::deserialize_reader(reader)?;
+        if variant == CRON_VARIANT {
+            let started_at = i64::deserialize_reader(reader)?;
+            return Ok(TriggerContextV1(TriggerContext::Cron {
+                started_at,
+                skipped: 0,
+            }));
+        }
+        TriggerContext::deserialize_reader(&mut [variant].as_slice().chain(reader))
+            .map(TriggerContextV1)
+    }
+}
+
+impl Space for TriggerContextV1 {
+    const MIN_SPACE: usize = TriggerContext::MIN_SPACE;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trigger_context_v1_layout() {
+        // Cron contexts of `Thread` accounts hold the threshold moment only.
+        let cron = TriggerContextV1(TriggerContext::Cron {
+            started_at: 60,
+            skipped: 0,
+        });
+        let data = cron.try_to_vec().unwrap();
+        assert_eq!(
+            data,
+            [[CRON_VARIANT].as_slice(), &60i64.to_le_bytes()].concat()
+        );
+        assert_eq!(TriggerContextV1::try_from_slice(&data).unwrap(), cron);
+
+        // Other contexts share the layout of `TriggerContext`.
+        let account_value = TriggerContext::AccountValue { value: -5 };
+        let data = account_value.try_to_vec().unwrap();
+        assert_eq!(TriggerContextV1(account_value).try_to_vec().unwrap(), data);
+        assert_eq!(
+            TriggerContextV1::try_from_slice(&data).unwrap(),
+            TriggerContextV1(account_value)
+        );
+    }
+}
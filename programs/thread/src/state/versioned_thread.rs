@@ -1,77 +1,127 @@
-use crate::{ClockData, ExecContext, SerializableInstruction, Thread, Trigger};
-use anchor_lang::{prelude::*, AccountDeserialize};
+use crate::{
+    ClockData, ExecContext, SerializableInstruction, Thread, ThreadExtension, ThreadV2, Trigger,
+};
+use anchor_lang::{prelude::*, AccountDeserialize, Discriminator};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum VersionedThread {
     V1(Thread),
+    V2(ThreadV2),
 }
 
 impl VersionedThread {
     pub fn authority(&self) -> Pubkey {
         match self {
             Self::V1(t) => t.authority,
+            Self::V2(t) => t.authority,
+        }
+    }
+
+    pub fn bump(&self) -> u8 {
+        match self {
+            Self::V1(t) => t.bump,
+            Self::V2(t) => t.bump,
         }
     }
 
     pub fn created_at(&self) -> ClockData {
         match self {
             Self::V1(t) => t.created_at.clone(),
+            Self::V2(t) => t.created_at.clone(),
         }
     }
 
     pub fn exec_context(&self) -> Option<ExecContext> {
         match self {
-            Self::V1(t) => t.exec_context,
+            Self::V1(t) => t.exec_context.map(Into::into),
+            Self::V2(t) => t.exec_context,
+        }
+    }
+
+    pub fn completed_cycle_at(&self, require_last_instruction: bool) -> Option<u64> {
+        match self {
+            Self::V1(t) => ThreadV2::from(t.clone()).completed_cycle_at(require_last_instruction),
+            Self::V2(t) => t.completed_cycle_at(require_last_instruction),
+        }
+    }
+
+    pub fn extension(&self) -> ThreadExtension {
+        match self {
+            Self::V1(t) => t.extension.0.clone(),
+            Self::V2(t) => t.extension.0.clone(),
+        }
+    }
+
+    pub fn is_expired(&self, unix_timestamp: i64) -> bool {
+        match self {
+            Self::V1(t) => ThreadV2::from(t.clone()).is_expired(unix_timestamp),
+            Self::V2(t) => t.is_expired(unix_timestamp),
         }
     }
 
     pub fn id(&self) -> Vec<u8> {
         match self {
             Self::V1(t) => t.id.clone(),
+            Self::V2(t) => t.id.clone(),
         }
     }
 
     pub fn domain(&self) -> Option<Vec<u8>> {
         match self {
             Self::V1(t) => t.domain.clone(),
+            Self::V2(t) => t.domain.clone(),
+        }
+    }
+
+    pub fn name(&self) -> Option<String> {
+        match self {
+            Self::V1(_) => None,
+            Self::V2(t) => t.name.clone(),
         }
     }
 
     pub fn next_instruction(&self) -> Option<SerializableInstruction> {
         match self {
             Self::V1(t) => t.next_instruction.clone(),
+            Self::V2(t) => t.next_instruction.clone(),
         }
     }
 
     pub fn paused(&self) -> bool {
         match self {
             Self::V1(t) => t.paused,
+            Self::V2(t) => t.paused,
         }
     }
 
     pub fn program_id(&self) -> Pubkey {
         match self {
-            Self::V1(_) => crate::ID,
+            Self::V1(_) | Self::V2(_) => crate::ID,
         }
     }
 
     pub fn pubkey(&self) -> Pubkey {
-        match self {
-            Self::V1(_) => Thread::pubkey(self.authority(), self.id(), self.domain()),
-        }
+        ThreadV2::pubkey(self.authority(), self.id(), self.domain())
     }
 
     pub fn rate_limit(&self) -> u64 {
         match self {
             Self::V1(t) => t.rate_limit,
+            Self::V2(t) => t.rate_limit,
         }
     }
 
     pub fn trigger(&self) -> Trigger {
         match self {
             Self::V1(t) => t.trigger.clone(),
+            Self::V2(t) => t.trigger.clone(),
         }
     }
+
+    /// Returns true if the thread must be migrated with `thread_migrate` before the program can run it.
+    pub fn needs_migration(&self) -> bool {
+        matches!(self, Self::V1(_))
+    }
 }
 
 impl AccountDeserialize for VersionedThread {
@@ -80,9 +130,17 @@ impl AccountDeserialize for VersionedThread {
     }
 
     fn try_deserialize_unchecked(buf: &mut &[u8]) -> anchor_lang::Result<Self> {
-        // Try first to deserialize into ThreadV2.
-        // If this fails, try to deserialize into ThreadV1.
-        Ok(VersionedThread::V1(Thread::try_deserialize(buf)?))
+        // Dispatch on the discriminator of the account.
+        match buf.get(..8) {
+            Some(discriminator) if discriminator == ThreadV2::discriminator() => {
+                Ok(VersionedThread::V2(ThreadV2::try_deserialize(buf)?))
+            }
+            Some(discriminator) if discriminator == Thread::discriminator() => {
+                Ok(VersionedThread::V1(Thread::try_deserialize(buf)?))
+            }
+            Some(_) => Err(ErrorCode::AccountDiscriminatorMismatch.into()),
+            None => Err(ErrorCode::AccountDiscriminatorNotFound.into()),
+        }
     }
 }
 
@@ -4,13 +4,19 @@ pub use sablier_thread_program::ID;
 
 pub mod state {
     pub use sablier_thread_program::state::{
-        ClockData, ExecContext, SerializableAccount, SerializableInstruction, Thread,
-        ThreadAccount, ThreadResponse, ThreadSettings, Trigger, TriggerContext,
+        ClockData, CronDialect, CronExclusions, CronOptions, ExecContext, ExecContextV1,
+        PauseReason, SerializableAccount, SerializableInstruction, Thread, ThreadAccount,
+        ThreadBudget, ThreadExtension, ThreadLifetime, ThreadResponse, ThreadSettings, ThreadV2,
+        Trigger, TriggerContext, TriggerContextV1, VersionedThread,
     };
 }
 
 pub mod utils {
+    pub use sablier_thread_program::state::Endianness;
     pub use sablier_thread_program::state::Equality;
+    pub use sablier_thread_program::state::OracleProvider;
+    pub use sablier_thread_program::state::PriceType;
+    pub use sablier_thread_program::state::ValueType;
     pub use sablier_thread_program::state::PAYER_PUBKEY;
 }
 
@@ -18,8 +24,8 @@ pub mod cpi {
     use anchor_lang::prelude::{CpiContext, Result};
 
     pub use sablier_thread_program::cpi::accounts::{
-        ThreadCreate, ThreadDelete, ThreadPause, ThreadReset, ThreadResume, ThreadUpdate,
-        ThreadWithdraw,
+        ThreadCreate, ThreadDelete, ThreadDeposit, ThreadMigrate, ThreadPause, ThreadReset,
+        ThreadResume, ThreadSignal, ThreadUpdate, ThreadWithdraw,
     };
 
     pub fn thread_create<'info>(
@@ -33,12 +39,50 @@ pub mod cpi {
         sablier_thread_program::cpi::thread_create(ctx, amount, id, domain, instructions, trigger)
     }
 
+    /// Creates a thread and applies settings to it in the same transaction,
+    /// e.g. to attach the address lookup tables its transactions are compiled with.
+    pub fn thread_create_with_settings<'info>(
+        ctx: CpiContext<'_, '_, '_, 'info, ThreadCreate<'info>>,
+        amount: u64,
+        id: Vec<u8>,
+        domain: Option<Vec<u8>>,
+        instructions: Vec<crate::state::SerializableInstruction>,
+        trigger: crate::state::Trigger,
+        settings: crate::state::ThreadSettings,
+    ) -> Result<()> {
+        let update_ctx = CpiContext::new_with_signer(
+            ctx.program.clone(),
+            ThreadUpdate {
+                authority: ctx.accounts.authority.clone(),
+                payer: ctx.accounts.payer.clone(),
+                system_program: ctx.accounts.system_program.clone(),
+                thread: ctx.accounts.thread.clone(),
+            },
+            ctx.signer_seeds,
+        );
+        thread_create(ctx, amount, id, domain, instructions, trigger)?;
+        thread_update(update_ctx, settings)
+    }
+
     pub fn thread_delete<'info>(
         ctx: CpiContext<'_, '_, '_, 'info, ThreadDelete<'info>>,
     ) -> Result<()> {
         sablier_thread_program::cpi::thread_delete(ctx)
     }
 
+    pub fn thread_deposit<'info>(
+        ctx: CpiContext<'_, '_, '_, 'info, ThreadDeposit<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        sablier_thread_program::cpi::thread_deposit(ctx, amount)
+    }
+
+    pub fn thread_migrate<'info>(
+        ctx: CpiContext<'_, '_, '_, 'info, ThreadMigrate<'info>>,
+    ) -> Result<()> {
+        sablier_thread_program::cpi::thread_migrate(ctx)
+    }
+
     pub fn thread_pause<'info>(
         ctx: CpiContext<'_, '_, '_, 'info, ThreadPause<'info>>,
     ) -> Result<()> {
@@ -57,6 +101,13 @@ pub mod cpi {
         sablier_thread_program::cpi::thread_reset(ctx)
     }
 
+    pub fn thread_signal<'info>(
+        ctx: CpiContext<'_, '_, '_, 'info, ThreadSignal<'info>>,
+        payload: u64,
+    ) -> Result<()> {
+        sablier_thread_program::cpi::thread_signal(ctx, payload)
+    }
+
     pub fn thread_update<'info>(
         ctx: CpiContext<'_, '_, '_, 'info, ThreadUpdate<'info>>,
         settings: crate::state::ThreadSettings,
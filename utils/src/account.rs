@@ -1,5 +1,9 @@
+use std::ops::{Deref, DerefMut};
+
 use anchor_lang::prelude::*;
 
+use crate::Space;
+
 pub trait AccountInfoExt<'info>: AsRef<AccountInfo<'info>> {
     fn realloc(&self, new_len: usize, zero_init: bool) -> Result<()> {
         self.as_ref().realloc(new_len, zero_init)?;
@@ -12,3 +16,112 @@ pub trait AccountInfoExt<'info>: AsRef<AccountInfo<'info>> {
 }
 
 impl<'info, T: AsRef<AccountInfo<'info>>> AccountInfoExt<'info> for T {}
+
+/// The marker written before an account extension.
+pub const ACCOUNT_EXTENSION_MARKER: [u8; 8] = *b"sablierx";
+
+/// Properties appended at the end of an account layout after accounts were created with it.
+///
+/// The extension is serialized after `ACCOUNT_EXTENSION_MARKER`. Accounts created before the extension existed,
+/// which may be followed by stale data, don't start with the marker and deserialize it to its default value.
+/// New properties must be appended at the end of `T`: they deserialize from zeros when reading older extensions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccountExtension<T>(pub T);
+
+impl<T: AnchorSerialize> AnchorSerialize for AccountExtension<T> {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&ACCOUNT_EXTENSION_MARKER)?;
+        self.0.serialize(writer)
+    }
+}
+
+impl<T: AnchorDeserialize + Default + Space> AnchorDeserialize for AccountExtension<T> {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        // The extension is the last field of the account, so it owns the rest of the data.
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        if !data.starts_with(&ACCOUNT_EXTENSION_MARKER) {
+            return Ok(Self::default());
+        }
+        data.resize(data.len().max(Self::MIN_SPACE), 0);
+        T::deserialize(&mut &data[ACCOUNT_EXTENSION_MARKER.len()..]).map(Self)
+    }
+}
+
+impl<T: Space> Space for AccountExtension<T> {
+    const MIN_SPACE: usize = ACCOUNT_EXTENSION_MARKER.len() + T::MIN_SPACE;
+}
+
+impl<T> Deref for AccountExtension<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for AccountExtension<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+    struct Legacy {
+        a: u64,
+    }
+
+    impl Space for Legacy {
+        const MIN_SPACE: usize = 8;
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+    struct Extended {
+        a: u64,
+        b: Option<u64>,
+    }
+
+    impl Space for Extended {
+        const MIN_SPACE: usize = 8 + 9;
+    }
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Debug, PartialEq)]
+    struct Account<T> {
+        id: u8,
+        extension: T,
+    }
+
+    #[test]
+    fn test_account_extension() {
+        // Accounts without the extension, possibly followed by stale data.
+        let data = [7u8, 1, 2, 3];
+        let account = Account::<AccountExtension<Extended>>::deserialize(&mut &data[..]).unwrap();
+        assert_eq!(account.id, 7);
+        assert_eq!(*account.extension, Extended::default());
+        let account = Account::<AccountExtension<Extended>>::deserialize(&mut &data[..1]).unwrap();
+        assert_eq!(*account.extension, Extended::default());
+
+        // Round trip.
+        let account = Account {
+            id: 7,
+            extension: AccountExtension(Extended { a: 1, b: Some(2) }),
+        };
+        let data = account.try_to_vec().unwrap();
+        assert_eq!(data.len(), 1 + AccountExtension::<Extended>::MIN_SPACE);
+        assert_eq!(Account::deserialize(&mut data.as_slice()).unwrap(), account);
+
+        // Extensions written before new properties were appended.
+        let data = Account {
+            id: 7,
+            extension: AccountExtension(Legacy { a: 1 }),
+        }
+        .try_to_vec()
+        .unwrap();
+        let account = Account::<AccountExtension<Extended>>::deserialize(&mut &data[..]).unwrap();
+        assert_eq!(*account.extension, Extended { a: 1, b: None });
+    }
+}
@@ -0,0 +1,46 @@
+use anchor_lang::solana_program::keccak;
+
+/// Computes a stable 64-bit fingerprint of the given byte slices.
+///
+/// The value is the first 8 bytes (little endian) of the keccak256 digest of the concatenated slices.
+/// Unlike `std::collections::hash_map::DefaultHasher`, this algorithm is fixed and will not change
+/// across Rust releases, so it is safe to persist on-chain and to recompute off-chain.
+pub fn stable_hash(vals: &[&[u8]]) -> u64 {
+    let digest = keccak::hashv(vals).to_bytes();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// Computes the data hash recorded by `Trigger::Account` threads for the watched byte range of an account.
+///
+/// The range `[offset, offset + size)` is clamped to the length of the account data.
+pub fn account_data_hash(data: &[u8], offset: u64, size: u64) -> u64 {
+    let start = (offset as usize).min(data.len());
+    let end = (offset.saturating_add(size) as usize).min(data.len());
+    stable_hash(&[&data[start..end]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stable_hash() {
+        // keccak256("") = c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470
+        assert_eq!(stable_hash(&[]), 0x3c23f7860146d2c5);
+        assert_eq!(stable_hash(&[b"sab", b"lier"]), stable_hash(&[b"sablier"]));
+        assert_ne!(stable_hash(&[b"sablier"]), stable_hash(&[b"sablieR"]));
+    }
+
+    #[test]
+    fn test_account_data_hash() {
+        let data = [1u8, 2, 3, 4, 5, 6, 7, 8];
+
+        assert_eq!(account_data_hash(&data, 2, 4), stable_hash(&[&data[2..6]]));
+
+        // The watched range is clamped to the account data.
+        assert_eq!(account_data_hash(&data, 4, 32), stable_hash(&[&data[4..]]));
+        assert_eq!(account_data_hash(&data, 32, 32), stable_hash(&[]));
+    }
+}
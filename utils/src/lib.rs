@@ -1,9 +1,13 @@
 pub mod account;
 pub mod explorer;
+pub mod hash;
+pub mod oracle;
 pub mod pubkey;
 pub mod pyth;
 pub mod space;
+pub mod switchboard;
 pub mod thread;
+pub mod token;
 
 use std::fmt::{Debug, Display, Formatter};
 
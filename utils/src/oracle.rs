@@ -0,0 +1,126 @@
+use anchor_lang::AccountDeserialize;
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    pyth::{self, PriceFeedMessage, PriceUpdateV2, VerificationLevel},
+    switchboard::{self, PullFeed},
+    thread::{Equality, OracleProvider},
+};
+
+/// A price read from an oracle feed, as `price * 10^exponent`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i128,
+    pub exponent: i32,
+    /// The unix timestamp at which the price was published.
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    /// Reads the price of an oracle feed account.
+    ///
+    /// Returns `None` if the account is not a feed of the given provider.
+    pub fn try_from_account(provider: OracleProvider, owner: &Pubkey, data: &[u8]) -> Option<Self> {
+        match provider {
+            OracleProvider::Pyth => {
+                if owner.ne(&pyth::ID) {
+                    return None;
+                }
+                let price_update = PriceUpdateV2::try_deserialize(&mut &data[..]).ok()?;
+                price_update
+                    .verification_level
+                    .gte(VerificationLevel::Full)
+                    .then(|| OraclePrice::from(&price_update.price_message))
+            }
+            OracleProvider::SwitchboardOnDemand => {
+                if !PullFeed::is_owned_by(owner) {
+                    return None;
+                }
+                PullFeed::try_from_account_data(data).map(OraclePrice::from)
+            }
+        }
+    }
+
+    /// Returns true if the price was published at most `max_age` seconds before `unix_timestamp`.
+    pub fn is_fresh(&self, unix_timestamp: i64, max_age: u64) -> bool {
+        self.publish_time
+            .saturating_add(max_age.try_into().unwrap_or(i64::MAX))
+            .ge(&unix_timestamp)
+    }
+
+    /// Returns true if the price meets a limit expressed as `limit * 10^limit_exponent`.
+    ///
+    /// `prior_price` is the price recorded at the last kickoff, in the exponent of the feed.
+    /// The limit is never met if the price and the limit cannot be scaled to a common exponent.
+    pub fn meets_limit(
+        &self,
+        equality: &Equality,
+        limit: i64,
+        limit_exponent: i32,
+        prior_price: Option<i128>,
+    ) -> bool {
+        // Scale the price and the limit to the smallest of both exponents.
+        let exponent = self.exponent.min(limit_exponent);
+        let scale = |value: i128, value_exponent: i32| {
+            10i128
+                .checked_pow(value_exponent.abs_diff(exponent))
+                .and_then(|factor| value.checked_mul(factor))
+        };
+        let (Some(price), Some(limit)) = (
+            scale(self.price, self.exponent),
+            scale(limit as i128, limit_exponent),
+        ) else {
+            return false;
+        };
+        let prior_price = match prior_price.map(|prior_price| scale(prior_price, self.exponent)) {
+            Some(None) => return false,
+            prior_price => prior_price.flatten(),
+        };
+
+        equality.is_met(price, limit, prior_price)
+    }
+}
+
+impl From<&PriceFeedMessage> for OraclePrice {
+    fn from(message: &PriceFeedMessage) -> Self {
+        OraclePrice {
+            price: message.price as i128,
+            exponent: message.exponent,
+            publish_time: message.publish_time,
+        }
+    }
+}
+
+impl From<PullFeed> for OraclePrice {
+    fn from(feed: PullFeed) -> Self {
+        OraclePrice {
+            price: feed.value,
+            exponent: -(switchboard::PRECISION as i32),
+            publish_time: feed.last_update_timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meets_limit() {
+        // A Switchboard price of 1.5.
+        let price = OraclePrice {
+            price: 1_500_000_000_000_000_000,
+            exponent: -18,
+            publish_time: 100,
+        };
+        let gte = Equality::GreaterThanOrEqual;
+
+        assert!(price.meets_limit(&gte, 15, -1, None));
+        assert!(!price.meets_limit(&gte, 2, 0, None));
+        assert!(price.meets_limit(&Equality::LessThanOrEqual, 2, 0, None));
+        assert!(!price.meets_limit(&gte, 1, 30, None));
+
+        assert!(price.is_fresh(160, 60));
+        assert!(!price.is_fresh(161, 60));
+    }
+}
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 use solana_program::{clock::Clock, pubkey as key, pubkey::Pubkey};
 
+use crate::{
+    oracle::OraclePrice,
+    thread::{Equality, PriceType},
+};
+
 pub const PYTH_PUSH_ORACLE_ID: Pubkey = key!("pythWSnswVUd12oZpeFP8e9CVaEqJg25g1Vtc2biRsT");
 pub const ID: Pubkey = key!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
 
@@ -160,6 +165,46 @@ impl anchor_lang::AccountDeserialize for PriceUpdateV2 {
     }
 }
 
+impl PriceFeedMessage {
+    /// Returns the spot or EMA price of the message, with its confidence interval.
+    pub fn price_of(&self, price_type: PriceType) -> (i64, u64) {
+        match price_type {
+            PriceType::Spot => (self.price, self.conf),
+            PriceType::Ema => (self.ema_price, self.ema_conf),
+        }
+    }
+
+    /// Returns true if the price of the given type meets a limit expressed as `limit * 10^limit_exponent`.
+    ///
+    /// `prior_price` is the price of the same type recorded at the last kickoff.
+    /// The limit is never met if the confidence interval is wider than `max_confidence_bps` basis points of the price,
+    /// or if the price and the limit cannot be scaled to a common exponent.
+    pub fn meets_limit(
+        &self,
+        price_type: PriceType,
+        max_confidence_bps: Option<u64>,
+        equality: &Equality,
+        limit: i64,
+        limit_exponent: i32,
+        prior_price: Option<i64>,
+    ) -> bool {
+        let (price, conf) = self.price_of(price_type);
+        if let Some(max_confidence_bps) = max_confidence_bps {
+            let max_conf = (price.unsigned_abs() as u128) * (max_confidence_bps as u128) / 10_000;
+            if (conf as u128).gt(&max_conf) {
+                return false;
+            }
+        }
+
+        OraclePrice {
+            price: price as i128,
+            exponent: self.exponent,
+            publish_time: self.publish_time,
+        }
+        .meets_limit(equality, limit, limit_exponent, prior_price.map(i128::from))
+    }
+}
+
 pub fn get_oracle_key(shard_id: u16, feed_id: FeedId) -> Pubkey {
     let (pubkey, _) =
         Pubkey::find_program_address(&[&shard_id.to_be_bytes(), &feed_id], &PYTH_PUSH_ORACLE_ID);
@@ -183,4 +228,38 @@ mod tests {
             key!("7UVimffxr9ow1uXYxsr4LHAcV58mLzhmwaeKvJ1pjLiE")
         );
     }
+
+    #[test]
+    fn test_meets_limit() {
+        // A price of 123.45 with a confidence interval of 0.10, and an EMA price of 120.00.
+        let message = PriceFeedMessage {
+            feed_id: [0; 32],
+            price: 12_345,
+            conf: 10,
+            exponent: -2,
+            publish_time: 0,
+            prev_publish_time: 0,
+            ema_price: 12_000,
+            ema_conf: 10,
+        };
+        let gte = Equality::GreaterThanOrEqual;
+
+        // The limit is normalized against the feed exponent.
+        assert!(message.meets_limit(PriceType::Spot, None, &gte, 123, 0, None));
+        assert!(!message.meets_limit(PriceType::Spot, None, &gte, 124, 0, None));
+        assert!(message.meets_limit(PriceType::Spot, None, &gte, 123_450, -3, None));
+        assert!(!message.meets_limit(PriceType::Ema, None, &gte, 123, 0, None));
+
+        // The confidence interval is 8.1 basis points of the price.
+        assert!(message.meets_limit(PriceType::Spot, Some(9), &gte, 123, 0, None));
+        assert!(!message.meets_limit(PriceType::Spot, Some(8), &gte, 123, 0, None));
+
+        // The prior price is scaled like the price.
+        let changed = Equality::ChangedByMoreThan;
+        assert!(message.meets_limit(PriceType::Spot, None, &changed, 1, 0, Some(12_200)));
+        assert!(!message.meets_limit(PriceType::Spot, None, &changed, 2, 0, Some(12_200)));
+
+        // Limits which cannot be scaled are never met.
+        assert!(!message.meets_limit(PriceType::Spot, None, &gte, i64::MAX, 40, None));
+    }
 }
@@ -30,6 +30,10 @@ impl<T: Space> Space for Option<T> {
     const MIN_SPACE: usize = 1 + T::MIN_SPACE;
 }
 
+impl<T: Space, const N: usize> Space for [T; N] {
+    const MIN_SPACE: usize = N * T::MIN_SPACE;
+}
+
 #[doc(hidden)]
 pub const fn max(a: usize, b: usize) -> usize {
     [a, b][(a < b) as usize]
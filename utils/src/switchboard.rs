@@ -0,0 +1,93 @@
+use solana_program::{pubkey as key, pubkey::Pubkey};
+
+/// The Switchboard On-Demand program on mainnet.
+pub const ON_DEMAND_MAINNET_ID: Pubkey = key!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+/// The Switchboard On-Demand program on devnet.
+pub const ON_DEMAND_DEVNET_ID: Pubkey = key!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2");
+
+/// The number of decimals of the values reported by pull feeds.
+pub const PRECISION: u32 = 18;
+
+const PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+
+// Offsets of the fields of `PullFeedAccountData`, including the account discriminator.
+const LAST_UPDATE_TIMESTAMP_OFFSET: usize = 2216;
+const RESULT_VALUE_OFFSET: usize = 2264;
+const RESULT_NUM_SAMPLES_OFFSET: usize = 2360;
+const RESULT_SLOT_OFFSET: usize = 2368;
+
+/// The length of a pull feed account, up to the end of its current result.
+const PULL_FEED_MIN_LEN: usize = 2392;
+
+/// The current result of a Switchboard On-Demand pull feed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PullFeed {
+    /// The median of the oracle submissions, with `PRECISION` decimals.
+    pub value: i128,
+    /// The number of submissions the result was computed from.
+    pub num_samples: u8,
+    /// The slot of the result.
+    pub slot: u64,
+    /// The unix timestamp of the last update.
+    pub last_update_timestamp: i64,
+}
+
+impl PullFeed {
+    /// Returns true if the account is owned by the Switchboard On-Demand program.
+    pub fn is_owned_by(owner: &Pubkey) -> bool {
+        owner.eq(&ON_DEMAND_MAINNET_ID) || owner.eq(&ON_DEMAND_DEVNET_ID)
+    }
+
+    /// Decodes the current result of a `PullFeedAccountData` account.
+    pub fn try_from_account_data(data: &[u8]) -> Option<Self> {
+        if data.len() < PULL_FEED_MIN_LEN || data[..8] != PULL_FEED_DISCRIMINATOR {
+            return None;
+        }
+
+        Some(PullFeed {
+            value: i128::from_le_bytes(read_bytes(data, RESULT_VALUE_OFFSET)?),
+            num_samples: data[RESULT_NUM_SAMPLES_OFFSET],
+            slot: u64::from_le_bytes(read_bytes(data, RESULT_SLOT_OFFSET)?),
+            last_update_timestamp: i64::from_le_bytes(read_bytes(
+                data,
+                LAST_UPDATE_TIMESTAMP_OFFSET,
+            )?),
+        })
+    }
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Option<[u8; N]> {
+    data.get(offset..offset + N)?.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pull_feed() {
+        let mut data = vec![0u8; PULL_FEED_MIN_LEN];
+        data[..8].copy_from_slice(&PULL_FEED_DISCRIMINATOR);
+        data[LAST_UPDATE_TIMESTAMP_OFFSET..LAST_UPDATE_TIMESTAMP_OFFSET + 8]
+            .copy_from_slice(&1_700_000_000i64.to_le_bytes());
+        data[RESULT_VALUE_OFFSET..RESULT_VALUE_OFFSET + 16]
+            .copy_from_slice(&(-1_500_000_000_000_000_000i128).to_le_bytes());
+        data[RESULT_NUM_SAMPLES_OFFSET] = 3;
+        data[RESULT_SLOT_OFFSET..RESULT_SLOT_OFFSET + 8].copy_from_slice(&42u64.to_le_bytes());
+
+        assert_eq!(
+            PullFeed::try_from_account_data(&data),
+            Some(PullFeed {
+                value: -1_500_000_000_000_000_000,
+                num_samples: 3,
+                slot: 42,
+                last_update_timestamp: 1_700_000_000,
+            })
+        );
+
+        // Other accounts are rejected.
+        assert_eq!(PullFeed::try_from_account_data(&data[..2000]), None);
+        data[0] = 0;
+        assert_eq!(PullFeed::try_from_account_data(&data), None);
+    }
+}
@@ -7,9 +7,9 @@ use anchor_lang::{
 };
 use sablier_macros::MinSpace;
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, fmt::Debug, hash::Hash};
+use std::{convert::TryFrom, fmt::Debug, hash::Hash, ops::Deref};
 
-use crate::pyth::FeedId;
+use crate::{pyth::FeedId, token::get_associated_token_address, Space};
 
 /// The stand-in pubkey for delegating a payer address to a worker. All workers are re-imbursed by the user for lamports spent during this delegation.
 pub const PAYER_PUBKEY: Pubkey = key!("Sab1ierPayer1111111111111111111111111111111");
@@ -63,7 +63,9 @@ pub enum Trigger {
     /// Allows a thread to be kicked off according to a one-time or recurring schedule.
     Cron {
         /// The schedule in cron syntax. Value must be parsable by the `sablier_cron` package.
-        #[max_len(32)]
+        /// It may be prefixed with the timezone it is evaluated in, e.g. `CRON_TZ=America/New_York 0 30 9 * * *`.
+        /// Its `H` tokens are hashed with the address of the thread, e.g. `H H * * * *` fires once an hour at an offset of its own.
+        #[max_len(MAX_CRON_SCHEDULE_LEN)]
         schedule: String,
 
         /// Boolean value indicating whether triggering moments may be skipped if they are missed (e.g. due to network downtime).
@@ -84,18 +86,302 @@ pub enum Trigger {
     Timestamp { unix_ts: i64 },
 
     /// Allows a thread to be kicked off according to a Pyth price feed movement.
+    ///
+    /// Deprecated: new threads cannot use it, use `PythPrice` instead. Existing threads compare the spot price
+    /// of shard 0 to the limit in whole units, as a `PythPrice` trigger with a `limit_exponent` of 0
+    /// and a `max_age` of `PYTH_STALENESS_THRESHOLD` would.
     Pyth {
         /// The price feed id to monitor.
         #[raw_space(32)]
         feed_id: FeedId, // TODO implement in the macro
         /// The equality operator (gte or lte) used to compare prices.
         equality: Equality,
-        /// The limit price to compare the Pyth feed to.
+        /// The limit price to compare the Pyth feed to, in whole units.
         limit: i64,
     },
 
     /// Allows a thread to be kicked off according to a period seconds number.
     Periodic { delay: u64 },
+
+    /// Allows a thread to be kicked off whenever an integer value in an account's data crosses a threshold.
+    /// Except with `ChangedByMoreThan`, the value must stop meeting the condition, which a kickoff records, before the trigger fires again.
+    AccountValue {
+        /// The address of the account to monitor.
+        address: Pubkey,
+        /// The byte offset of the value in the account data.
+        offset: u64,
+        /// The integer type of the value.
+        value_type: ValueType,
+        /// The byte order of the value.
+        endianness: Endianness,
+        /// The equality operator used to compare the value to the threshold.
+        equality: Equality,
+        /// The threshold to compare the value to.
+        threshold: i128,
+    },
+
+    /// Allows a thread to be kicked off whenever the balance of an SPL Token or Token-2022 account crosses a threshold.
+    /// Except with `ChangedByMoreThan`, the balance must stop meeting the condition, which a kickoff records, before the trigger fires again.
+    TokenBalance {
+        /// The address of the token account to monitor.
+        address: Pubkey,
+        /// The equality operator used to compare the balance to the threshold.
+        equality: Equality,
+        /// The threshold amount, in base units of the mint.
+        threshold: u64,
+    },
+
+    /// Allows a thread to be kicked off according to a Pyth price feed, with configurable freshness and confidence checks.
+    PythPrice {
+        /// The price feed id to monitor.
+        #[raw_space(32)]
+        feed_id: FeedId,
+        /// The shard of the Pyth push oracle account holding the price feed.
+        shard_id: u16,
+        /// Whether to compare the spot or the EMA price.
+        price_type: PriceType,
+        /// The equality operator used to compare the price to the limit.
+        equality: Equality,
+        /// The limit price, as `limit * 10^limit_exponent`.
+        limit: i64,
+        /// The exponent of the limit price.
+        limit_exponent: i32,
+        /// The maximum age of the price update, in seconds.
+        max_age: u64,
+        /// The maximum confidence interval, in basis points of the price.
+        max_confidence_bps: Option<u64>,
+    },
+
+    /// Allows a thread to be kicked off according to the price of an oracle feed.
+    Oracle {
+        /// The oracle network publishing the feed.
+        provider: OracleProvider,
+        /// The address of the feed account (a Pyth price update or a Switchboard On-Demand pull feed).
+        feed: Pubkey,
+        /// The equality operator used to compare the price to the limit.
+        equality: Equality,
+        /// The limit price, as `limit * 10^limit_exponent`.
+        limit: i64,
+        /// The exponent of the limit price.
+        limit_exponent: i32,
+        /// The maximum age of the price, in seconds.
+        max_age: u64,
+    },
+
+    /// Allows a thread to be kicked off whenever another thread completes an execution cycle.
+    Chain {
+        /// The address of the upstream thread.
+        upstream: Pubkey,
+        /// If true, only cycles which executed the last instruction of the upstream thread's instruction set count as completed.
+        require_last_instruction: bool,
+    },
+
+    /// Allows a thread to be kicked off whenever it receives a signal through the `thread_signal` instruction.
+    Signal {
+        /// An additional signer allowed to signal the thread (e.g. a program PDA), besides the thread authority.
+        signaler: Option<Pubkey>,
+    },
+
+    /// Allows a thread to be kicked off every given number of slots.
+    SlotInterval {
+        /// The number of slots between kickoffs.
+        interval: u64,
+        /// The slot of the first kickoff. If none, the first kickoff is one interval after the thread's creation.
+        start_slot: Option<u64>,
+        /// Boolean value indicating whether triggering slots may be skipped if they are missed.
+        /// If false, any "missed" triggering slots will simply be executed as soon as possible.
+        skippable: bool,
+    },
+
+    /// Allows a thread to be kicked off by a primary trigger, but only while all of the given conditions hold.
+    /// The thread's exec context records the trigger context of the primary trigger.
+    /// Moments of cron, periodic and slot interval primaries at which the conditions don't hold are skipped.
+    Compound {
+        /// The trigger driving the thread's kickoffs. Compound triggers cannot be nested.
+        #[raw_space(PRIMARY_TRIGGER_SPACE)]
+        trigger: PrimaryTrigger,
+        /// The conditions which must all be met at kickoff.
+        #[max_len(MAX_TRIGGER_CONDITIONS)]
+        conditions: Vec<TriggerCondition>,
+    },
+}
+
+/// The primary trigger of a compound trigger.
+///
+/// Borsh derives bound every field type, which cannot be satisfied for a `Box<Trigger>` inside `Trigger`.
+/// This wrapper implements the serialization traits by hand to break that cycle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimaryTrigger(pub Box<Trigger>);
+
+impl AnchorSerialize for PrimaryTrigger {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        self.0.serialize(writer)
+    }
+}
+
+impl AnchorDeserialize for PrimaryTrigger {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(PrimaryTrigger(Box::new(Trigger::deserialize_reader(
+            reader,
+        )?)))
+    }
+}
+
+impl Deref for PrimaryTrigger {
+    type Target = Trigger;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Trigger> for PrimaryTrigger {
+    fn from(trigger: Trigger) -> Self {
+        PrimaryTrigger(Box::new(trigger))
+    }
+}
+
+/// The maximum number of conditions a compound trigger may hold.
+pub const MAX_TRIGGER_CONDITIONS: usize = 4;
+
+/// The length of cron schedules space is reserved for, enough for a timezone prefix such as `CRON_TZ=America/New_York`.
+pub const MAX_CRON_SCHEDULE_LEN: usize = 64;
+
+/// The space reserved for the primary trigger of a compound trigger.
+/// This must be kept greater than or equal to the space of the largest non-compound trigger variant.
+pub const PRIMARY_TRIGGER_SPACE: usize = {
+    let cron_space = 1 // discriminator
+        + (4 + MAX_CRON_SCHEDULE_LEN) // schedule
+        + bool::MIN_SPACE; // skippable
+    let pyth_price_space = 1 // discriminator
+        + 32 // feed_id
+        + u16::MIN_SPACE // shard_id
+        + PriceType::MIN_SPACE // price_type
+        + Equality::MIN_SPACE // equality
+        + i64::MIN_SPACE // limit
+        + i32::MIN_SPACE // limit_exponent
+        + u64::MIN_SPACE // max_age
+        + <Option<u64>>::MIN_SPACE; // max_confidence_bps
+    if cron_space > pyth_price_space {
+        cron_space
+    } else {
+        pyth_price_space
+    }
+};
+
+impl Trigger {
+    /// Returns the trigger driving the thread's kickoffs, unwrapping compound triggers.
+    pub fn primary(&self) -> &Trigger {
+        match self {
+            Trigger::Compound { trigger, .. } => trigger,
+            trigger => trigger,
+        }
+    }
+
+    /// Returns the conditions which must hold at kickoff, if any.
+    pub fn conditions(&self) -> &[TriggerCondition] {
+        match self {
+            Trigger::Compound { conditions, .. } => conditions,
+            _ => &[],
+        }
+    }
+
+    /// Returns true if the primary trigger is deprecated, which new threads cannot use.
+    pub fn is_deprecated(&self) -> bool {
+        matches!(self.primary(), Trigger::Pyth { .. })
+    }
+
+    /// Returns true if the trigger is well-formed.
+    /// Compound triggers cannot be nested and hold at most `MAX_TRIGGER_CONDITIONS` conditions.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Trigger::SlotInterval { interval, .. } => interval.gt(&0),
+            Trigger::Compound {
+                trigger,
+                conditions,
+            } => {
+                !matches!(trigger.deref(), Trigger::Compound { .. })
+                    && trigger.is_valid()
+                    && conditions.len().le(&MAX_TRIGGER_CONDITIONS)
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns a `TokenBalance` trigger watching the associated token account of a wallet for the given mint.
+    pub fn token_balance_of(
+        wallet: &Pubkey,
+        mint: &Pubkey,
+        token_program_id: &Pubkey,
+        equality: Equality,
+        threshold: u64,
+    ) -> Self {
+        Trigger::TokenBalance {
+            address: get_associated_token_address(wallet, mint, token_program_id),
+            equality,
+            threshold,
+        }
+    }
+}
+
+/// A condition gating the primary trigger of a compound trigger.
+///
+/// Conditions are evaluated against the current state at kickoff only. No prior value is recorded for them,
+/// so `Equality::ChangedByMoreThan` is always met.
+#[derive(AnchorDeserialize, AnchorSerialize, MinSpace, Debug, Clone, PartialEq)]
+pub enum TriggerCondition {
+    /// Met if the current unix timestamp is greater than or equal to the given timestamp.
+    NotBefore { unix_ts: i64 },
+
+    /// Met if the current unix timestamp is less than or equal to the given timestamp.
+    NotAfter { unix_ts: i64 },
+
+    /// Met if the Pyth price of a feed compares to the limit, as with `Trigger::PythPrice`.
+    Pyth {
+        /// The price feed id to read.
+        #[raw_space(32)]
+        feed_id: FeedId,
+        /// The shard of the Pyth push oracle account the feed is read from.
+        shard_id: u16,
+        /// Whether to compare the spot or the EMA price.
+        price_type: PriceType,
+        /// The equality operator used to compare the price to the limit.
+        equality: Equality,
+        /// The limit price, as `limit * 10^limit_exponent`.
+        limit: i64,
+        /// The exponent of the limit price.
+        limit_exponent: i32,
+        /// The maximum age of the price update, in seconds.
+        max_age: u64,
+        /// The maximum confidence interval, in basis points of the price.
+        max_confidence_bps: Option<u64>,
+    },
+
+    /// Met if an integer value in an account's data compares to the threshold.
+    AccountValue {
+        /// The address of the account to read.
+        address: Pubkey,
+        /// The byte offset of the value in the account data.
+        offset: u64,
+        /// The integer type of the value.
+        value_type: ValueType,
+        /// The byte order of the value.
+        endianness: Endianness,
+        /// The equality operator used to compare the value to the threshold.
+        equality: Equality,
+        /// The threshold to compare the value to.
+        threshold: i128,
+    },
+
+    /// Met if the balance of an SPL Token or Token-2022 account compares to the threshold.
+    TokenBalance {
+        /// The address of the token account to read.
+        address: Pubkey,
+        /// The equality operator used to compare the balance to the threshold.
+        equality: Equality,
+        /// The threshold amount, in base units of the mint.
+        threshold: u64,
+    },
 }
 
 /// Operators for describing how to compare two values to one another.  
@@ -104,6 +390,120 @@ pub enum Trigger {
 pub enum Equality {
     GreaterThanOrEqual,
     LessThanOrEqual,
+    Equal,
+    NotEqual,
+    /// The value moved by more than the threshold since the last kickoff.
+    ChangedByMoreThan,
+}
+
+impl Equality {
+    /// Returns true if the value satisfies the operator against the threshold.
+    /// `prior` is the value recorded at the last kickoff and is only used by `ChangedByMoreThan`,
+    /// which is always satisfied when there is no prior value.
+    pub fn is_met(&self, value: i128, threshold: i128, prior: Option<i128>) -> bool {
+        match self {
+            Equality::GreaterThanOrEqual => value.ge(&threshold),
+            Equality::LessThanOrEqual => value.le(&threshold),
+            Equality::Equal => value.eq(&threshold),
+            Equality::NotEqual => value.ne(&threshold),
+            Equality::ChangedByMoreThan => prior.map_or(true, |prior| {
+                value.abs_diff(prior).gt(&threshold.unsigned_abs())
+            }),
+        }
+    }
+
+    /// Returns true if the value meets the threshold while the prior value did not, i.e. the value crossed the threshold.
+    /// `ChangedByMoreThan` is measured from the prior value, so it is crossed whenever it is met.
+    pub fn is_crossed(&self, value: i128, threshold: i128, prior: Option<i128>) -> bool {
+        match self {
+            Equality::ChangedByMoreThan => self.is_met(value, threshold, prior),
+            _ => {
+                self.is_met(value, threshold, None)
+                    && !prior.is_some_and(|prior| self.is_met(prior, threshold, None))
+            }
+        }
+    }
+
+    /// Returns true if the prior value met the threshold while the value does not, which re-arms `is_crossed`.
+    pub fn is_released(&self, value: i128, threshold: i128, prior: Option<i128>) -> bool {
+        match self {
+            Equality::ChangedByMoreThan => false,
+            _ => {
+                !self.is_met(value, threshold, None)
+                    && prior.is_some_and(|prior| self.is_met(prior, threshold, None))
+            }
+        }
+    }
+}
+
+/// The oracle networks a thread can read prices from.
+#[derive(AnchorDeserialize, AnchorSerialize, MinSpace, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum OracleProvider {
+    Pyth,
+    SwitchboardOnDemand,
+}
+
+/// The price of a Pyth feed to compare.
+#[derive(AnchorDeserialize, AnchorSerialize, MinSpace, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum PriceType {
+    /// The latest aggregate price.
+    Spot,
+    /// The exponentially-weighted moving average price.
+    Ema,
+}
+
+/// The integer types which can be read from account data.
+#[derive(AnchorDeserialize, AnchorSerialize, MinSpace, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ValueType {
+    U8,
+    U16,
+    U32,
+    U64,
+    I64,
+}
+
+/// The byte order of an integer stored in account data.
+#[derive(AnchorDeserialize, AnchorSerialize, MinSpace, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+macro_rules! read_int {
+    ($ty:ty, $bytes:expr, $endianness:expr) => {{
+        let bytes = $bytes.try_into().ok()?;
+        match $endianness {
+            Endianness::Little => <$ty>::from_le_bytes(bytes) as i128,
+            Endianness::Big => <$ty>::from_be_bytes(bytes) as i128,
+        }
+    }};
+}
+
+impl ValueType {
+    /// The number of bytes used by the value.
+    pub fn size(&self) -> usize {
+        match self {
+            ValueType::U8 => 1,
+            ValueType::U16 => 2,
+            ValueType::U32 => 4,
+            ValueType::U64 | ValueType::I64 => 8,
+        }
+    }
+
+    /// Reads the value at the given byte offset of the data.
+    /// Returns `None` if the value does not fit in the data.
+    pub fn read(&self, data: &[u8], offset: u64, endianness: Endianness) -> Option<i128> {
+        let start = usize::try_from(offset).ok()?;
+        let bytes = data.get(start..start.checked_add(self.size())?)?;
+        let value = match self {
+            ValueType::U8 => read_int!(u8, bytes, endianness),
+            ValueType::U16 => read_int!(u16, bytes, endianness),
+            ValueType::U32 => read_int!(u32, bytes, endianness),
+            ValueType::U64 => read_int!(u64, bytes, endianness),
+            ValueType::I64 => read_int!(i64, bytes, endianness),
+        };
+        Some(value)
+    }
 }
 
 /// A response value target programs can return to update the thread.
@@ -226,3 +626,145 @@ impl SerializableAccount {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primary_trigger_space() {
+        let triggers = [
+            Trigger::Account {
+                address: Pubkey::default(),
+                offset: 0,
+                size: 0,
+            },
+            Trigger::Cron {
+                schedule: "*".repeat(MAX_CRON_SCHEDULE_LEN),
+                skippable: true,
+            },
+            Trigger::Pyth {
+                feed_id: [0; 32],
+                equality: Equality::GreaterThanOrEqual,
+                limit: 0,
+            },
+            Trigger::AccountValue {
+                address: Pubkey::default(),
+                offset: 0,
+                value_type: ValueType::I64,
+                endianness: Endianness::Little,
+                equality: Equality::Equal,
+                threshold: 0,
+            },
+            Trigger::TokenBalance {
+                address: Pubkey::default(),
+                equality: Equality::Equal,
+                threshold: 0,
+            },
+            Trigger::PythPrice {
+                feed_id: [0; 32],
+                shard_id: 0,
+                price_type: PriceType::Ema,
+                equality: Equality::Equal,
+                limit: 0,
+                limit_exponent: 0,
+                max_age: 0,
+                max_confidence_bps: Some(0),
+            },
+        ];
+        for trigger in triggers {
+            assert!(trigger.try_to_vec().unwrap().len() <= PRIMARY_TRIGGER_SPACE);
+        }
+    }
+
+    #[test]
+    fn test_compound_trigger_is_valid() {
+        let compound = Trigger::Compound {
+            trigger: Trigger::Now.into(),
+            conditions: vec![TriggerCondition::NotBefore { unix_ts: 0 }],
+        };
+        assert!(compound.is_valid());
+        assert_eq!(compound.primary(), &Trigger::Now);
+        assert_eq!(compound.conditions().len(), 1);
+
+        let nested = Trigger::Compound {
+            trigger: compound.into(),
+            conditions: vec![],
+        };
+        assert!(!nested.is_valid());
+
+        let too_many = Trigger::Compound {
+            trigger: Trigger::Now.into(),
+            conditions: vec![TriggerCondition::NotAfter { unix_ts: 0 }; MAX_TRIGGER_CONDITIONS + 1],
+        };
+        assert!(!too_many.is_valid());
+
+        let every_slot = Trigger::SlotInterval {
+            interval: 0,
+            start_slot: None,
+            skippable: true,
+        };
+        assert!(!every_slot.is_valid());
+    }
+
+    #[test]
+    fn test_value_type_read() {
+        let data = [
+            0x01, 0x02, 0x03, 0x04, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        ];
+
+        assert_eq!(ValueType::U8.read(&data, 0, Endianness::Little), Some(0x01));
+        assert_eq!(
+            ValueType::U16.read(&data, 0, Endianness::Little),
+            Some(0x0201)
+        );
+        assert_eq!(ValueType::U16.read(&data, 0, Endianness::Big), Some(0x0102));
+        assert_eq!(
+            ValueType::U32.read(&data, 0, Endianness::Big),
+            Some(0x01020304)
+        );
+        assert_eq!(ValueType::I64.read(&data, 4, Endianness::Little), Some(-1));
+        assert_eq!(
+            ValueType::U64.read(&data, 4, Endianness::Little),
+            Some(u64::MAX as i128)
+        );
+
+        // The value must fit in the data.
+        assert_eq!(ValueType::U64.read(&data, 5, Endianness::Little), None);
+        assert_eq!(
+            ValueType::U8.read(&data, u64::MAX, Endianness::Little),
+            None
+        );
+    }
+
+    #[test]
+    fn test_equality_is_met() {
+        assert!(Equality::GreaterThanOrEqual.is_met(10, 10, None));
+        assert!(!Equality::GreaterThanOrEqual.is_met(9, 10, None));
+        assert!(Equality::LessThanOrEqual.is_met(9, 10, None));
+        assert!(Equality::Equal.is_met(10, 10, None));
+        assert!(Equality::NotEqual.is_met(9, 10, None));
+
+        assert!(Equality::ChangedByMoreThan.is_met(100, 5, None));
+        assert!(Equality::ChangedByMoreThan.is_met(94, 5, Some(100)));
+        assert!(!Equality::ChangedByMoreThan.is_met(105, 5, Some(100)));
+    }
+
+    #[test]
+    fn test_equality_is_crossed() {
+        assert!(Equality::GreaterThanOrEqual.is_crossed(10, 10, None));
+        assert!(Equality::GreaterThanOrEqual.is_crossed(10, 10, Some(9)));
+        assert!(!Equality::GreaterThanOrEqual.is_crossed(11, 10, Some(10)));
+        assert!(!Equality::GreaterThanOrEqual.is_crossed(9, 10, Some(8)));
+        assert!(Equality::LessThanOrEqual.is_crossed(9, 10, Some(11)));
+        assert!(!Equality::NotEqual.is_crossed(8, 10, Some(9)));
+        assert!(Equality::ChangedByMoreThan.is_crossed(94, 5, Some(100)));
+        assert!(!Equality::ChangedByMoreThan.is_crossed(105, 5, Some(100)));
+
+        assert!(Equality::GreaterThanOrEqual.is_released(9, 10, Some(10)));
+        assert!(!Equality::GreaterThanOrEqual.is_released(9, 10, None));
+        assert!(!Equality::GreaterThanOrEqual.is_released(9, 10, Some(8)));
+        assert!(!Equality::GreaterThanOrEqual.is_released(11, 10, Some(10)));
+        assert!(!Equality::ChangedByMoreThan.is_released(100, 5, Some(100)));
+    }
+}
@@ -0,0 +1,80 @@
+use solana_program::{pubkey as key, pubkey::Pubkey};
+
+pub const TOKEN_PROGRAM_ID: Pubkey = key!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = key!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    key!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// The length of the base token account layout, shared by SPL Token and Token-2022.
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// The offset of the little-endian `u64` amount in the base token account layout.
+pub const AMOUNT_OFFSET: usize = 64;
+const STATE_OFFSET: usize = 108;
+
+/// The Token-2022 account type discriminator stored right after the base layout of extended accounts.
+const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+/// Returns the associated token account of a wallet for the given mint and token program.
+pub fn get_associated_token_address(
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program_id: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program_id.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Reads the `amount` of an initialized SPL Token or Token-2022 account.
+///
+/// Returns `None` if the account is not owned by a token program or does not hold a token account.
+pub fn read_token_amount(owner: &Pubkey, data: &[u8]) -> Option<u64> {
+    let is_token_account = match data.len() {
+        len if len < TOKEN_ACCOUNT_LEN => false,
+        TOKEN_ACCOUNT_LEN => owner.eq(&TOKEN_PROGRAM_ID) || owner.eq(&TOKEN_2022_PROGRAM_ID),
+        // Token-2022 accounts with extensions carry their account type after the base layout.
+        _ => owner.eq(&TOKEN_2022_PROGRAM_ID) && data[TOKEN_ACCOUNT_LEN] == ACCOUNT_TYPE_ACCOUNT,
+    };
+    if !is_token_account || data[STATE_OFFSET] == 0 {
+        return None;
+    }
+
+    let amount = data.get(AMOUNT_OFFSET..AMOUNT_OFFSET + 8)?;
+    Some(u64::from_le_bytes(amount.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_account(amount: u64, len: usize) -> Vec<u8> {
+        let mut data = vec![0u8; len];
+        data[AMOUNT_OFFSET..AMOUNT_OFFSET + 8].copy_from_slice(&amount.to_le_bytes());
+        data[STATE_OFFSET] = 1;
+        data
+    }
+
+    #[test]
+    fn test_read_token_amount() {
+        let data = token_account(42, TOKEN_ACCOUNT_LEN);
+        assert_eq!(read_token_amount(&TOKEN_PROGRAM_ID, &data), Some(42));
+        assert_eq!(read_token_amount(&TOKEN_2022_PROGRAM_ID, &data), Some(42));
+        assert_eq!(read_token_amount(&Pubkey::default(), &data), None);
+
+        // Uninitialized accounts hold no balance.
+        let mut data = data;
+        data[STATE_OFFSET] = 0;
+        assert_eq!(read_token_amount(&TOKEN_PROGRAM_ID, &data), None);
+
+        // Token-2022 accounts with extensions.
+        let mut data = token_account(7, TOKEN_ACCOUNT_LEN + 8);
+        data[TOKEN_ACCOUNT_LEN] = ACCOUNT_TYPE_ACCOUNT;
+        assert_eq!(read_token_amount(&TOKEN_2022_PROGRAM_ID, &data), Some(7));
+        assert_eq!(read_token_amount(&TOKEN_PROGRAM_ID, &data), None);
+        data[TOKEN_ACCOUNT_LEN] = 1;
+        assert_eq!(read_token_amount(&TOKEN_2022_PROGRAM_ID, &data), None);
+    }
+}